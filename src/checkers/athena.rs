@@ -3,11 +3,9 @@ use lemmeknow::Identifier;
 use log::trace;
 
 use super::{
+    checker_expression::CheckerExpression,
     checker_type::{Check, Checker},
-    english::EnglishChecker,
     human_checker,
-    lemmeknow_checker::LemmeKnow,
-    regex_checker::RegexChecker,
 };
 
 /// Athena checker runs all other checkers
@@ -30,35 +28,23 @@ impl Check for Checker<Athena> {
 
     fn check(&self, text: &str) -> CheckResult {
         let config = get_config();
-        // Only run regex if its in the config
-        if config.regex.is_some() {
-            trace!("running regex");
-            let regex_checker = Checker::<RegexChecker>::new();
-            let regex_result = regex_checker.check(text);
-            if regex_result.is_identified {
-                let mut check_res = CheckResult::new(&regex_checker);
-                check_res.is_identified = human_checker::human_checker(&regex_result);
-                return check_res;
-            }
-        } else {
-            // In Ciphey if the user uses the regex checker all the other checkers turn off
-            // This is because they are looking for one specific bit of information so will not want the other checkers
-            // TODO: wrap all checkers in oncecell so we only create them once!
-            let lemmeknow = Checker::<LemmeKnow>::new();
-            let lemmeknow_result = lemmeknow.check(text);
-            if lemmeknow_result.is_identified {
-                let mut check_res = CheckResult::new(&lemmeknow);
-                check_res.is_identified = human_checker::human_checker(&lemmeknow_result);
-                return check_res;
+        // In Ciphey if the user uses the regex checker all the other checkers turn off,
+        // the default expression keeps that. Users can combine checkers with `checkers` in the config.
+        // TODO: wrap all checkers in oncecell so we only create them once!
+        let default_expression;
+        let expression = match &config.checkers {
+            Some(expression) => expression,
+            None => {
+                default_expression = CheckerExpression::default_for(config);
+                &default_expression
             }
+        };
+        trace!("Checking with {}", expression);
 
-            let english = Checker::<EnglishChecker>::new();
-            let english_result = english.check(text);
-            if english_result.is_identified {
-                let mut check_res = CheckResult::new(&english);
-                check_res.is_identified = human_checker::human_checker(&english_result);
-                return check_res;
-            }
+        let mut check_res = expression.check(text, config);
+        if check_res.is_identified {
+            check_res.is_identified = human_checker::human_checker(&check_res);
+            return check_res;
         }
 
         CheckResult::new(self)
@@ -0,0 +1,518 @@
+use std::{fmt, str::FromStr};
+
+use log::trace;
+
+use super::{
+    athena::Athena,
+    checker_result::CheckResult,
+    checker_type::{Check, Checker},
+    crib_checker::CribChecker,
+    english::EnglishChecker,
+    file_type_checker::FileTypeChecker,
+    flag_checker::FlagChecker,
+    language_checker::LanguageChecker,
+    lemmeknow_checker::LemmeKnow,
+    quadgram_checker::QuadgramChecker,
+    regex_checker::RegexChecker,
+    structured_data_checker::StructuredDataChecker,
+};
+use crate::config::Config;
+
+/// The checkers an expression can name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamedChecker {
+    /// The Regex Checker, which needs `regex` set in the config
+    Regex,
+    /// The Crib Checker, which needs `cribs` set in the config
+    Crib,
+    /// The Flag Checker
+    Flag,
+    /// The File Type Checker
+    FileType,
+    /// The LemmeKnow Checker
+    LemmeKnow,
+    /// The Structured Data Checker
+    StructuredData,
+    /// The English Checker
+    English,
+    /// The Language Checker
+    Language,
+    /// The Quadgram Checker
+    Quadgram,
+}
+
+impl NamedChecker {
+    /// Every checker an expression can use
+    pub const ALL: [NamedChecker; 9] = [
+        NamedChecker::Regex,
+        NamedChecker::Crib,
+        NamedChecker::Flag,
+        NamedChecker::FileType,
+        NamedChecker::LemmeKnow,
+        NamedChecker::StructuredData,
+        NamedChecker::English,
+        NamedChecker::Language,
+        NamedChecker::Quadgram,
+    ];
+
+    /// The name used for this checker in expressions
+    pub const fn name(&self) -> &'static str {
+        match self {
+            NamedChecker::Regex => "regex",
+            NamedChecker::Crib => "crib",
+            NamedChecker::Flag => "flag",
+            NamedChecker::FileType => "file_type",
+            NamedChecker::LemmeKnow => "lemmeknow",
+            NamedChecker::StructuredData => "structured_data",
+            NamedChecker::English => "english",
+            NamedChecker::Language => "language",
+            NamedChecker::Quadgram => "quadgram",
+        }
+    }
+
+    /// Runs the checker on the text
+    fn check(&self, text: &str, config: &Config) -> CheckResult {
+        match self {
+            NamedChecker::Regex => {
+                let regex_checker = Checker::<RegexChecker>::new();
+                // Without a regex there's nothing to match against
+                if config.regex.is_none() {
+                    return CheckResult::new(&regex_checker);
+                }
+                regex_checker.check(text)
+            }
+            NamedChecker::Crib => Checker::<CribChecker>::new().check(text),
+            NamedChecker::Flag => Checker::<FlagChecker>::new().check(text),
+            NamedChecker::FileType => Checker::<FileTypeChecker>::new().check(text),
+            NamedChecker::LemmeKnow => Checker::<LemmeKnow>::new().check(text),
+            NamedChecker::StructuredData => Checker::<StructuredDataChecker>::new().check(text),
+            NamedChecker::English => Checker::<EnglishChecker>::new().check(text),
+            NamedChecker::Language => Checker::<LanguageChecker>::new().check(text),
+            NamedChecker::Quadgram => Checker::<QuadgramChecker>::new().check(text),
+        }
+    }
+}
+
+/// An expression deciding what counts as plaintext, like `regex AND english`
+/// or `(lemmeknow OR flag) AND NOT english>=0.9`.
+/// `AND` binds tighter than `OR`, and `&&`, `||` and `!` work too.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckerExpression {
+    /// Passes when the checker identifies the text with at least this confidence.
+    /// Without a threshold the `confidence_threshold` from the config is used.
+    Checker(NamedChecker, Option<f64>),
+    /// Passes when every expression passes
+    And(Vec<CheckerExpression>),
+    /// Passes when any expression passes, trying them in order
+    Or(Vec<CheckerExpression>),
+    /// Passes when the expression does not
+    Not(Box<CheckerExpression>),
+}
+
+impl CheckerExpression {
+    /// The checkers Athena runs when the user doesn't give an expression.
+    /// With a regex or cribs only those checkers run, as the user is looking for something specific.
+    /// Otherwise every checker runs, most specific first.
+    pub fn default_for(config: &Config) -> CheckerExpression {
+        let mut specific = Vec::new();
+        if config.regex.is_some() {
+            specific.push(CheckerExpression::Checker(NamedChecker::Regex, None));
+        }
+        if !config.cribs.is_empty() {
+            specific.push(CheckerExpression::Checker(NamedChecker::Crib, None));
+        }
+        if !specific.is_empty() {
+            return flatten(specific, CheckerExpression::And);
+        }
+        let english_enabled = config.languages.iter().any(|language| language == "en");
+        // Other languages run before quadgrams, as European languages
+        // share enough quadgrams with English to fool them.
+        // Structured data runs after English, so prose isn't mistaken for a CSV file
+        let checkers = [
+            (NamedChecker::Flag, true),
+            (NamedChecker::FileType, true),
+            (NamedChecker::LemmeKnow, true),
+            (NamedChecker::English, english_enabled),
+            (NamedChecker::StructuredData, true),
+            (NamedChecker::Language, true),
+            (NamedChecker::Quadgram, english_enabled),
+        ];
+        CheckerExpression::Or(
+            checkers
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(checker, _)| CheckerExpression::Checker(*checker, None))
+                .collect(),
+        )
+    }
+
+    /// Runs the expression on the text.
+    /// The result is the one from the checker that decided it, so its description is kept.
+    /// AND results have the lowest confidence of their parts and all of their descriptions.
+    pub fn check(&self, text: &str, config: &Config) -> CheckResult {
+        match self {
+            CheckerExpression::Checker(checker, threshold) => {
+                trace!("Running {} from the checker expression", checker.name());
+                let mut result = checker.check(text, config);
+                let threshold = threshold.unwrap_or(config.confidence_threshold);
+                result.is_identified = result.is_identified && result.confidence >= threshold;
+                result
+            }
+            CheckerExpression::And(expressions) => {
+                let mut results = Vec::with_capacity(expressions.len());
+                for expression in expressions {
+                    let result = expression.check(text, config);
+                    if !result.is_identified {
+                        return result;
+                    }
+                    results.push(result);
+                }
+                let confidence = results
+                    .iter()
+                    .map(|result| result.confidence)
+                    .fold(1.0, f64::min);
+                let description = results
+                    .iter()
+                    .map(|result| result.description.as_str())
+                    .filter(|description| !description.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                // The flag (or whatever was found) might come from any of them
+                let matched = results.iter().find_map(|result| result.matched.clone());
+                let mut result = results.swap_remove(0);
+                result.confidence = confidence;
+                result.description = description;
+                result.matched = matched;
+                result
+            }
+            CheckerExpression::Or(expressions) => {
+                // An empty OR has nothing that could pass
+                let mut last = CheckResult::new(&Checker::<Athena>::new());
+                for expression in expressions {
+                    last = expression.check(text, config);
+                    if last.is_identified {
+                        break;
+                    }
+                }
+                last
+            }
+            CheckerExpression::Not(expression) => {
+                let mut result = expression.check(text, config);
+                result.is_identified = !result.is_identified;
+                result.confidence = 1.0 - result.confidence;
+                // Whatever the checker found is why it didn't pass
+                result.matched = None;
+                if result.is_identified {
+                    result.description = format!("not {}", result.checker_name);
+                }
+                result
+            }
+        }
+    }
+}
+
+impl fmt::Display for CheckerExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Brackets around everything but single checkers keep the output unambiguous
+        let write_part =
+            |f: &mut fmt::Formatter<'_>, expression: &CheckerExpression| match expression {
+                CheckerExpression::Checker(..) | CheckerExpression::Not(_) => {
+                    write!(f, "{expression}")
+                }
+                _ => write!(f, "({expression})"),
+            };
+        let write_joined =
+            |f: &mut fmt::Formatter<'_>, expressions: &[CheckerExpression], operator: &str| {
+                for (i, expression) in expressions.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " {operator} ")?;
+                    }
+                    write_part(f, expression)?;
+                }
+                Ok(())
+            };
+        match self {
+            CheckerExpression::Checker(checker, None) => write!(f, "{}", checker.name()),
+            CheckerExpression::Checker(checker, Some(threshold)) => {
+                write!(f, "{}>={}", checker.name(), threshold)
+            }
+            CheckerExpression::And(expressions) => write_joined(f, expressions, "AND"),
+            CheckerExpression::Or(expressions) => write_joined(f, expressions, "OR"),
+            CheckerExpression::Not(expression) => {
+                write!(f, "NOT ")?;
+                write_part(f, expression)
+            }
+        }
+    }
+}
+
+/// The pieces a checker expression is made of
+#[derive(Debug, PartialEq)]
+enum Token {
+    /// A checker name, or a number after `>=`
+    Word(String),
+    /// `AND` or `&&`
+    And,
+    /// `OR` or `||`
+    Or,
+    /// `NOT` or `!`
+    Not,
+    /// `>=`
+    AtLeast,
+    /// `(`
+    Open,
+    /// `)`
+    Close,
+}
+
+/// Splits an expression into tokens
+fn tokenise(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '!' => Token::Not,
+            '&' | '|' | '>' => {
+                let second = if c == '>' { '=' } else { c };
+                if chars.next_if_eq(&second).is_none() {
+                    return Err(format!("expected '{c}{second}' in checker expression"));
+                }
+                match c {
+                    '&' => Token::And,
+                    '|' => Token::Or,
+                    _ => Token::AtLeast,
+                }
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    word.push(c);
+                }
+                match word.to_ascii_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Word(word),
+                }
+            }
+            c => return Err(format!("unexpected '{c}' in checker expression")),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// A recursive descent parser over the tokens of an expression
+struct Parser {
+    /// The tokens, reversed so we can pop from the front
+    tokens: Vec<Token>,
+}
+
+impl Parser {
+    /// or := and (OR and)*
+    fn parse_or(&mut self) -> Result<CheckerExpression, String> {
+        let mut expressions = vec![self.parse_and()?];
+        while self.tokens.last() == Some(&Token::Or) {
+            self.tokens.pop();
+            expressions.push(self.parse_and()?);
+        }
+        Ok(flatten(expressions, CheckerExpression::Or))
+    }
+
+    /// and := not (AND not)*
+    fn parse_and(&mut self) -> Result<CheckerExpression, String> {
+        let mut expressions = vec![self.parse_not()?];
+        while self.tokens.last() == Some(&Token::And) {
+            self.tokens.pop();
+            expressions.push(self.parse_not()?);
+        }
+        Ok(flatten(expressions, CheckerExpression::And))
+    }
+
+    /// not := NOT not | ( or ) | checker [>= number]
+    fn parse_not(&mut self) -> Result<CheckerExpression, String> {
+        match self.tokens.pop() {
+            Some(Token::Not) => Ok(CheckerExpression::Not(Box::new(self.parse_not()?))),
+            Some(Token::Open) => {
+                let expression = self.parse_or()?;
+                match self.tokens.pop() {
+                    Some(Token::Close) => Ok(expression),
+                    _ => Err("missing ')' in checker expression".to_string()),
+                }
+            }
+            Some(Token::Word(name)) => {
+                let checker = NamedChecker::ALL
+                    .into_iter()
+                    .find(|checker| checker.name().eq_ignore_ascii_case(&name))
+                    .ok_or_else(|| {
+                        let names: Vec<&str> =
+                            NamedChecker::ALL.iter().map(NamedChecker::name).collect();
+                        format!(
+                            "unknown checker '{name}', expected one of {}",
+                            names.join(", ")
+                        )
+                    })?;
+                if self.tokens.last() != Some(&Token::AtLeast) {
+                    return Ok(CheckerExpression::Checker(checker, None));
+                }
+                self.tokens.pop();
+                let threshold = match self.tokens.pop() {
+                    Some(Token::Word(number)) => number.parse::<f64>().ok(),
+                    _ => None,
+                }
+                .filter(|threshold| (0.0..=1.0).contains(threshold))
+                .ok_or_else(|| format!("expected a number from 0.0 to 1.0 after '{name}>='"))?;
+                Ok(CheckerExpression::Checker(checker, Some(threshold)))
+            }
+            _ => Err("expected a checker name, NOT or '(' in checker expression".to_string()),
+        }
+    }
+}
+
+/// A single expression doesn't need wrapping in AND or OR
+fn flatten(
+    mut expressions: Vec<CheckerExpression>,
+    wrap: fn(Vec<CheckerExpression>) -> CheckerExpression,
+) -> CheckerExpression {
+    if expressions.len() == 1 {
+        expressions.remove(0)
+    } else {
+        wrap(expressions)
+    }
+}
+
+impl FromStr for CheckerExpression {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut tokens = tokenise(input)?;
+        tokens.reverse();
+        let mut parser = Parser { tokens };
+        let expression = parser.parse_or()?;
+        if !parser.tokens.is_empty() {
+            return Err("unexpected text after the end of the checker expression".to_string());
+        }
+        Ok(expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CheckerExpression, NamedChecker};
+    use crate::config::Config;
+
+    fn checker(checker: NamedChecker) -> CheckerExpression {
+        CheckerExpression::Checker(checker, None)
+    }
+
+    #[test]
+    fn test_parse_and_binds_tighter_than_or() {
+        let expression: CheckerExpression = "lemmeknow OR flag AND english".parse().unwrap();
+        assert_eq!(
+            expression,
+            CheckerExpression::Or(vec![
+                checker(NamedChecker::LemmeKnow),
+                CheckerExpression::And(vec![
+                    checker(NamedChecker::Flag),
+                    checker(NamedChecker::English)
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_symbols_brackets_and_thresholds() {
+        let expression: CheckerExpression = "(regex || flag) && !english>=0.9".parse().unwrap();
+        assert_eq!(
+            expression,
+            CheckerExpression::And(vec![
+                CheckerExpression::Or(vec![
+                    checker(NamedChecker::Regex),
+                    checker(NamedChecker::Flag)
+                ]),
+                CheckerExpression::Not(Box::new(CheckerExpression::Checker(
+                    NamedChecker::English,
+                    Some(0.9)
+                ))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_display_parses_back() {
+        let expression: CheckerExpression = "(regex OR flag) AND NOT quadgram>=0.5 AND file_type"
+            .parse()
+            .unwrap();
+        let printed = expression.to_string();
+        assert_eq!(
+            printed,
+            "(regex OR flag) AND NOT quadgram>=0.5 AND file_type"
+        );
+        assert_eq!(printed.parse::<CheckerExpression>().unwrap(), expression);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("english AND"
+            .parse::<CheckerExpression>()
+            .unwrap_err()
+            .contains("expected a checker name"));
+        assert!("klingon"
+            .parse::<CheckerExpression>()
+            .unwrap_err()
+            .contains("unknown checker 'klingon'"));
+        assert!("english>=2".parse::<CheckerExpression>().is_err());
+        assert!("(english".parse::<CheckerExpression>().is_err());
+        assert!("english flag".parse::<CheckerExpression>().is_err());
+    }
+
+    #[test]
+    fn test_check_or_uses_first_match() {
+        let expression: CheckerExpression = "lemmeknow OR flag".parse().unwrap();
+        let result = expression.check("192.168.0.1", &Config::default());
+        assert!(result.is_identified);
+        assert_eq!(result.checker_name, "LemmeKnow Checker");
+    }
+
+    #[test]
+    fn test_check_and_needs_every_checker() {
+        let config = Config::default();
+        let expression: CheckerExpression = "flag AND structured_data".parse().unwrap();
+        let result = expression.check(
+            r#"{"category": "forensics", "answer": "flag{n3st3d}"}"#,
+            &config,
+        );
+        assert!(result.is_identified);
+        assert!(result.description.starts_with("CTF flag, JSON document"));
+        assert_eq!(result.matched.as_deref(), Some("flag{n3st3d}"));
+        assert!(!expression.check("flag{n3st3d}", &config).is_identified);
+    }
+
+    #[test]
+    fn test_check_not_and_thresholds() {
+        let config = Config::default();
+        let not_quadgram: CheckerExpression = "NOT quadgram".parse().unwrap();
+        assert!(
+            not_quadgram
+                .check("xqzvkjwpfhgtbnmc", &config)
+                .is_identified
+        );
+        // Structured data is as confident as its words are English,
+        // which config keys rarely are all the way
+        let document = r#"{"db_host": "localhost", "port": 5432}"#;
+        let loose: CheckerExpression = "structured_data".parse().unwrap();
+        let strict: CheckerExpression = "structured_data>=0.99".parse().unwrap();
+        assert!(loose.check(document, &config).is_identified);
+        assert!(!strict.check(document, &config).is_identified);
+    }
+
+    #[test]
+    fn test_regex_without_a_regex_fails() {
+        let expression: CheckerExpression = "regex".parse().unwrap();
+        assert!(!expression.check("hello", &Config::default()).is_identified);
+    }
+}
@@ -16,6 +16,13 @@ pub struct CheckResult {
     pub checker_description: &'static str,
     /// Link to more info about checker
     pub link: &'static str,
+    /// How sure the checker is that this is plaintext, from 0.0 to 1.0
+    /// Athena compares this against the threshold in the config
+    /// and the searcher uses it to pick between competing plaintexts.
+    pub confidence: f64,
+    /// The part of the text the checker found, like a CTF flag in a sentence.
+    /// None when the checker accepts the text as a whole.
+    pub matched: Option<String>,
 }
 
 /// To save time we have a default
@@ -33,6 +40,8 @@ impl CheckResult {
             checker_description: checker_used.description,
             description: "".to_string(),
             link: checker_used.link,
+            confidence: 0.0,
+            matched: None,
         }
     }
 }
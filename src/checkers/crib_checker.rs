@@ -0,0 +1,264 @@
+use std::{fmt, str::FromStr};
+
+use lemmeknow::Identifier;
+
+use super::checker_type::{Check, Checker};
+use crate::{checkers::checker_result::CheckResult, config::get_config};
+use log::trace;
+
+/// The Crib Checker looks for known pieces of the plaintext (cribs), like `flag{` at the start.
+/// Unlike the regex checker it ignores case, whitespace and punctuation, so `Hello, World`
+/// matches the crib `hello world`.
+pub struct CribChecker;
+
+/// Where in the plaintext a crib has to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CribPosition {
+    /// Anywhere in the text
+    Anywhere,
+    /// At the very start, ignoring leading whitespace and punctuation
+    Start,
+    /// At the very end, ignoring trailing whitespace and punctuation
+    End,
+    /// Starting at this character of the text
+    Offset(usize),
+}
+
+/// A known piece of the plaintext
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crib {
+    /// The text we expect to find
+    pub text: String,
+    /// Where we expect to find it
+    pub position: CribPosition,
+}
+
+impl FromStr for Crib {
+    type Err = String;
+
+    /// Parses `text`, `start:text`, `end:text`, `anywhere:text` or `12:text` (at character 12)
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (position, text) = match input.split_once(':') {
+            Some(("start", text)) => (CribPosition::Start, text),
+            Some(("end", text)) => (CribPosition::End, text),
+            Some(("anywhere", text)) => (CribPosition::Anywhere, text),
+            Some((offset, text))
+                if !offset.is_empty() && offset.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                let offset = offset
+                    .parse()
+                    .map_err(|_| format!("crib offset {offset} is too big"))?;
+                (CribPosition::Offset(offset), text)
+            }
+            _ => (CribPosition::Anywhere, input),
+        };
+        if text.trim().is_empty() {
+            return Err("a crib can't be empty".to_string());
+        }
+        Ok(Crib {
+            text: text.to_string(),
+            position,
+        })
+    }
+}
+
+impl fmt::Display for Crib {
+    /// Writes the crib the way it's parsed, so `start:flag{` stays `start:flag{`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            // Text with a colon could be mistaken for a position
+            CribPosition::Anywhere if self.text.contains(':') => {
+                write!(f, "anywhere:{}", self.text)
+            }
+            CribPosition::Anywhere => write!(f, "{}", self.text),
+            CribPosition::Start => write!(f, "start:{}", self.text),
+            CribPosition::End => write!(f, "end:{}", self.text),
+            CribPosition::Offset(offset) => write!(f, "{offset}:{}", self.text),
+        }
+    }
+}
+
+impl Crib {
+    /// Cribs made only of punctuation, like `}`, would fold away to nothing
+    /// so we only fold punctuation when the crib has letters or digits.
+    fn folds_punctuation(&self) -> bool {
+        self.text.chars().any(char::is_alphanumeric)
+    }
+
+    /// The crib as it is compared against text
+    fn folded(&self) -> Vec<char> {
+        fold(&self.text, self.folds_punctuation())
+            .into_iter()
+            .map(|(_, c)| c)
+            .collect()
+    }
+
+    /// Every place the crib could line up with the text, as the index into the folded text.
+    /// Only places allowed by the crib's position are returned.
+    fn candidate_starts(&self, folded_text: &[(usize, char)], crib_length: usize) -> Vec<usize> {
+        if crib_length > folded_text.len() {
+            return vec![];
+        }
+        let last_start = folded_text.len() - crib_length;
+        match self.position {
+            CribPosition::Anywhere => (0..=last_start).collect(),
+            CribPosition::Start => vec![0],
+            CribPosition::End => vec![last_start],
+            CribPosition::Offset(offset) => folded_text[..=last_start]
+                .iter()
+                .position(|(index, _)| *index == offset)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Returns the character the crib starts at in the text, if it's there
+    pub fn find(&self, text: &str) -> Option<usize> {
+        let crib = self.folded();
+        let folded_text = fold(text, self.folds_punctuation());
+        self.candidate_starts(&folded_text, crib.len())
+            .into_iter()
+            .find(|start| {
+                folded_text[*start..start + crib.len()]
+                    .iter()
+                    .map(|(_, c)| *c)
+                    .eq(crib.iter().copied())
+            })
+            .map(|start| folded_text[start].0)
+    }
+
+    /// Lines the crib up with the ciphertext everywhere its position allows.
+    /// Each alignment is a list of (ciphertext char, crib char) pairs, both folded,
+    /// so decoders which recover keys (like caesar) can work out the key directly
+    /// instead of trying every one.
+    pub fn alignments(&self, ciphertext: &str) -> Vec<Vec<(char, char)>> {
+        let crib = self.folded();
+        let folded_text = fold(ciphertext, self.folds_punctuation());
+        self.candidate_starts(&folded_text, crib.len())
+            .into_iter()
+            .map(|start| {
+                folded_text[start..start + crib.len()]
+                    .iter()
+                    .map(|(_, c)| *c)
+                    .zip(crib.iter().copied())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Lowercases the text and drops whitespace (and punctuation, if asked),
+/// keeping the character index each remaining char came from.
+fn fold(text: &str, drop_punctuation: bool) -> Vec<(usize, char)> {
+    text.chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_whitespace() && (!drop_punctuation || c.is_alphanumeric()))
+        .flat_map(|(index, c)| c.to_lowercase().map(move |lower| (index, lower)))
+        .collect()
+}
+
+impl Check for Checker<CribChecker> {
+    fn new() -> Self {
+        Checker {
+            name: "Crib Checker",
+            description:
+                "Looks for known pieces of the plaintext, ignoring case, whitespace and punctuation",
+            link: "https://en.wikipedia.org/wiki/Known-plaintext_attack",
+            tags: vec!["crib", "known plaintext"],
+            expected_runtime: 0.01,
+            popularity: 1.0,
+            lemmeknow_config: Identifier::default(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn check(&self, text: &str) -> CheckResult {
+        trace!("Checking {} for cribs", text);
+        let mut result = CheckResult::new(self);
+        result.text = text.to_string();
+        let cribs = &get_config().cribs;
+        if cribs.is_empty() {
+            return result;
+        }
+
+        // Every crib is known plaintext, so they all have to be there
+        let mut found = Vec::with_capacity(cribs.len());
+        for crib in cribs {
+            match crib.find(text) {
+                Some(index) => found.push(format!("crib {:?} at character {}", crib.text, index)),
+                None => return result,
+            }
+        }
+        result.is_identified = true;
+        result.description = format!("Found {}", found.join(", "));
+        result.confidence = 1.0;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Crib, CribPosition};
+
+    fn crib(input: &str) -> Crib {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_positions() {
+        assert_eq!(crib("flag{").position, CribPosition::Anywhere);
+        assert_eq!(crib("start:flag{").position, CribPosition::Start);
+        assert_eq!(crib("end:}").position, CribPosition::End);
+        assert_eq!(crib("12:hello").position, CribPosition::Offset(12));
+        assert_eq!(crib("12:hello").text, "hello");
+        // Only known prefixes are positions, other colons are part of the crib
+        assert_eq!(crib("Date: today").text, "Date: today");
+        assert_eq!(crib("anywhere:10:30").text, "10:30");
+        assert!("start:".parse::<Crib>().is_err());
+    }
+
+    #[test]
+    fn test_display_parses_back() {
+        for input in [
+            "flag{",
+            "start:flag{",
+            "end:}",
+            "12:hello",
+            "anywhere:10:30",
+        ] {
+            assert_eq!(crib(input).to_string(), input);
+            assert_eq!(crib(&crib(input).to_string()), crib(input));
+        }
+    }
+
+    #[test]
+    fn test_find_folds_case_whitespace_and_punctuation() {
+        assert_eq!(crib("hello world").find("Well, HELLO-World!"), Some(6));
+        assert_eq!(crib("hello world").find("goodbye world"), None);
+    }
+
+    #[test]
+    fn test_find_at_positions() {
+        assert_eq!(crib("start:flag").find("  FLAG{abc}"), Some(2));
+        assert_eq!(crib("start:abc").find("flag{abc}"), None);
+        assert_eq!(crib("end:the end").find("this is the end."), Some(8));
+        assert_eq!(crib("8:the").find("this is the end"), Some(8));
+        assert_eq!(crib("0:the").find("this is the end"), None);
+    }
+
+    #[test]
+    fn test_find_punctuation_only_crib() {
+        assert_eq!(crib("end:}").find("flag{abc}"), Some(8));
+        assert_eq!(crib("end:}").find("flag{abc"), None);
+    }
+
+    #[test]
+    fn test_alignments() {
+        assert_eq!(
+            crib("start:ab").alignments("X Y z"),
+            vec![vec![('x', 'a'), ('y', 'b')]]
+        );
+        assert_eq!(crib("ab").alignments("xyz").len(), 2);
+        assert!(crib("abcd").alignments("xyz").is_empty());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::checkers::checker_result::CheckResult;
-use crate::storage;
+use crate::config::get_config;
+use crate::storage::{self, normalise_string};
 use lemmeknow::Identifier;
 use log::{debug, trace};
+use std::collections::HashSet;
 
 use crate::checkers::checker_type::{Check, Checker};
 
@@ -45,6 +47,8 @@ impl Check for Checker<EnglishChecker> {
             checker_description: self.description,
             description: filename.to_string(),
             link: self.link,
+            confidence: 0.0,
+            matched: None,
         };
 
         // After we've normalised our string, if we find it's a length 0 we don't do anything
@@ -54,6 +58,10 @@ impl Check for Checker<EnglishChecker> {
         }
 
         let split_input = input.split(' ');
+        let word_count = split_input.clone().count() as f64;
+        let user_wordlists = &get_config().wordlists;
+        // How many words each dictionary matched, so we can say where they came from
+        let mut sources: Vec<(&str, usize)> = Vec::new();
 
         // loop through all the words in the input
         for word in split_input {
@@ -64,12 +72,13 @@ impl Check for Checker<EnglishChecker> {
             // We should probably just use a single dictionary and assign the filenames to the values in the dictionary.
             // Like {"hello": "English.txt"} etc.
             // If we're using multiple dictionaries we may also have duplicated words which is inefficient.
-            if storage::DICTIONARIES
-                .iter()
-                .any(|(_, words)| words.contains(word))
-            {
-                trace!("Found word {} in English", word);
+            if let Some(source) = dictionary_containing(word, user_wordlists) {
+                trace!("Found word {} in {}", word, source);
                 words_found += 1.0;
+                match sources.iter_mut().find(|(name, _)| *name == source) {
+                    Some((_, count)) => *count += 1,
+                    None => sources.push((source, 1)),
+                }
             }
 
             trace!(
@@ -78,44 +87,54 @@ impl Check for Checker<EnglishChecker> {
                 words_found,
                 input.len()
             );
-            // TODO: We are also typecasting to f64 instead of usize, which costs CPU cycles.
-            if words_found / (input.split(' ').count()) as f64 > PLAINTEXT_DETECTION_PERCENTAGE {
-                debug!("Found {} words in {}", words_found, original_input);
-                debug!(
-                    "Returning from English checker successfully with {}",
-                    original_input
-                );
-                result.is_identified = true;
-                break;
-            }
+        }
+
+        // We check every word rather than stopping at 40%,
+        // so the confidence is the real ratio of English words
+        result.confidence = words_found / word_count;
+        if result.confidence > PLAINTEXT_DETECTION_PERCENTAGE {
+            debug!("Found {} words in {}", words_found, original_input);
+            debug!(
+                "Returning from English checker successfully with {}",
+                original_input
+            );
+            result.is_identified = true;
+            // Most matched dictionary first
+            sources.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+            let names: Vec<&str> = sources.iter().map(|(name, _)| *name).collect();
+            result.description = format!("{filename} (words from {})", names.join(", "));
         }
 
         result
     }
 }
 
-/// Strings look funny, they might have commas, be uppercase etc
-/// This normalises the string so English checker can work on it
-/// In particular it:
-/// Removes punctuation from the string
-/// Lowercases the string
-fn normalise_string(input: &str) -> String {
-    // The replace function supports patterns https://doc.rust-lang.org/std/str/pattern/trait.Pattern.html#impl-Pattern%3C%27a%3E-3
-    // TODO add more punctuation
-    input
-        .to_ascii_lowercase()
-        .chars()
-        .filter(|x| !x.is_ascii_punctuation())
-        .collect()
+/// Returns the name of the first dictionary with this word in it.
+/// The built in dictionaries are searched first, then the user's wordlists in the order given.
+fn dictionary_containing<'a>(
+    word: &str,
+    user_wordlists: &'a [(String, HashSet<String>)],
+) -> Option<&'a str> {
+    storage::DICTIONARIES
+        .iter()
+        .find(|(_, words)| words.contains(word))
+        .map(|(name, _)| *name)
+        .or_else(|| {
+            user_wordlists
+                .iter()
+                .find(|(_, words)| words.contains(word))
+                .map(|(name, _)| name.as_str())
+        })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::checkers::english::normalise_string;
+    use crate::checkers::english::dictionary_containing;
     use crate::checkers::{
         checker_type::{Check, Checker},
         english::EnglishChecker,
     };
+    use std::collections::HashSet;
 
     #[test]
     fn test_check_basic() {
@@ -150,22 +169,6 @@ mod tests {
         let checker = Checker::<EnglishChecker>::new();
         assert!(checker.check("preinterview hello dog").is_identified);
     }
-    #[test]
-    fn test_check_normalise_string_works_with_lowercasing() {
-        let x = normalise_string("Hello Dear");
-        assert_eq!(x, "hello dear")
-    }
-    #[test]
-    fn test_check_normalise_string_works_with_puncuation() {
-        let x = normalise_string("Hello, Dear");
-        assert_eq!(x, "hello dear")
-    }
-    #[test]
-    fn test_check_normalise_string_works_with_messy_puncuation() {
-        let x = normalise_string(".He/ll?O, Dea!r");
-        assert_eq!(x, "hello dear")
-    }
-
     #[test]
     fn test_checker_works_with_puncuation_and_lowercase() {
         let checker = Checker::<EnglishChecker>::new();
@@ -182,6 +185,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_check_confidence_is_ratio_of_english_words() {
+        let checker = Checker::<EnglishChecker>::new();
+        let result = checker.check("hello dog nnnnnnnnnnn llllllll");
+        assert!((result.confidence - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_user_wordlist_is_searched_and_named() {
+        let wordlists = [
+            (
+                "projects.txt".to_string(),
+                HashSet::from(["zorblax".to_string()]),
+            ),
+            (
+                "products.txt".to_string(),
+                HashSet::from(["zorblax".to_string(), "widgetron".to_string()]),
+            ),
+        ];
+        // The first list given with the word gets the credit, every time
+        assert_eq!(
+            dictionary_containing("zorblax", &wordlists),
+            Some("projects.txt")
+        );
+        assert_eq!(
+            dictionary_containing("widgetron", &wordlists),
+            Some("products.txt")
+        );
+        assert_eq!(dictionary_containing("qwfpgjluy", &wordlists), None);
+    }
+
     #[test]
     fn test_check_fail_single_puncuation_char() {
         let checker = Checker::<EnglishChecker>::new();
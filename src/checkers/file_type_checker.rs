@@ -0,0 +1,210 @@
+use lemmeknow::Identifier;
+
+use super::checker_type::{Check, Checker};
+use crate::checkers::checker_result::CheckResult;
+use log::trace;
+
+/// The File Type Checker recognises binary files like PNGs or ZIPs by their magic bytes.
+/// This is the struct for it.
+pub struct FileTypeChecker;
+
+/// A file format we can recognise from the bytes it starts with
+pub struct FileSignature {
+    /// The name of the format, like "PNG image"
+    pub name: &'static str,
+    /// The MIME type of the format
+    pub mime_type: &'static str,
+    /// The bytes every file of this format starts with
+    pub magic: &'static [u8],
+}
+
+/// The file formats we recognise.
+/// Signatures shorter than 3 bytes (like BMP's "BM") match too much ordinary text, so they are left out.
+pub const FILE_SIGNATURES: [FileSignature; 16] = [
+    FileSignature {
+        name: "PNG image",
+        mime_type: "image/png",
+        magic: b"\x89PNG\r\n\x1a\n",
+    },
+    FileSignature {
+        name: "JPEG image",
+        mime_type: "image/jpeg",
+        magic: b"\xff\xd8\xff",
+    },
+    FileSignature {
+        name: "GIF image",
+        mime_type: "image/gif",
+        magic: b"GIF87a",
+    },
+    FileSignature {
+        name: "GIF image",
+        mime_type: "image/gif",
+        magic: b"GIF89a",
+    },
+    FileSignature {
+        name: "PDF document",
+        mime_type: "application/pdf",
+        magic: b"%PDF-",
+    },
+    FileSignature {
+        name: "ZIP archive",
+        mime_type: "application/zip",
+        magic: b"PK\x03\x04",
+    },
+    FileSignature {
+        name: "ZIP archive",
+        mime_type: "application/zip",
+        magic: b"PK\x05\x06",
+    },
+    FileSignature {
+        name: "ELF executable",
+        mime_type: "application/x-executable",
+        magic: b"\x7fELF",
+    },
+    FileSignature {
+        name: "gzip archive",
+        mime_type: "application/gzip",
+        magic: b"\x1f\x8b\x08",
+    },
+    FileSignature {
+        name: "7-Zip archive",
+        mime_type: "application/x-7z-compressed",
+        magic: b"7z\xbc\xaf\x27\x1c",
+    },
+    FileSignature {
+        name: "RAR archive",
+        mime_type: "application/vnd.rar",
+        magic: b"Rar!\x1a\x07",
+    },
+    FileSignature {
+        name: "Java class file",
+        mime_type: "application/java-vm",
+        magic: b"\xca\xfe\xba\xbe",
+    },
+    FileSignature {
+        name: "WebAssembly module",
+        mime_type: "application/wasm",
+        magic: b"\0asm",
+    },
+    FileSignature {
+        name: "SQLite database",
+        mime_type: "application/vnd.sqlite3",
+        magic: b"SQLite format 3\0",
+    },
+    FileSignature {
+        name: "pcap capture",
+        mime_type: "application/vnd.tcpdump.pcap",
+        magic: b"\xd4\xc3\xb2\xa1",
+    },
+    FileSignature {
+        name: "pcapng capture",
+        mime_type: "application/vnd.tcpdump.pcap",
+        magic: b"\x0a\x0d\x0d\x0a",
+    },
+];
+
+impl Check for Checker<FileTypeChecker> {
+    fn new() -> Self {
+        Checker {
+            name: "File Type Checker",
+            description:
+                "Recognises files such as PNG, PDF, ZIP, ELF and JPEG from their magic bytes",
+            link: "https://en.wikipedia.org/wiki/List_of_file_signatures",
+            tags: vec!["file", "magic", "forensics"],
+            expected_runtime: 0.01,
+            popularity: 0.8,
+            lemmeknow_config: Identifier::default(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn check(&self, text: &str) -> CheckResult {
+        trace!("Checking for file magic bytes");
+        let mut result = CheckResult::new(self);
+        result.text = text.to_string();
+
+        if let Some(signature) = detect_file_type(&text_to_bytes(text)) {
+            result.is_identified = true;
+            result.description = format!("{} ({})", signature.name, signature.mime_type);
+            // Longer signatures are less likely to match by accident
+            result.confidence = (signature.magic.len() as f64 / 4.0).min(1.0);
+        }
+        result
+    }
+}
+
+/// Returns the file format these bytes start with, if we know it
+pub fn detect_file_type(bytes: &[u8]) -> Option<&'static FileSignature> {
+    FILE_SIGNATURES
+        .iter()
+        .find(|signature| bytes.starts_with(signature.magic))
+}
+
+/// Turns decoded text back into the bytes it came from.
+/// Decoders keep binary files as one char per byte (Latin-1), so if that gives us
+/// a file we recognise we return those bytes. Otherwise the text is just text, so UTF-8.
+pub fn text_to_bytes(text: &str) -> Vec<u8> {
+    let latin1: Option<Vec<u8>> = text
+        .chars()
+        .map(|c| u8::try_from(u32::from(c)).ok())
+        .collect();
+    match latin1 {
+        Some(bytes) if detect_file_type(&bytes).is_some() => bytes,
+        _ => text.as_bytes().to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkers::{
+        checker_type::{Check, Checker},
+        file_type_checker::{text_to_bytes, FileTypeChecker},
+    };
+
+    #[test]
+    fn test_check_png() {
+        let checker = Checker::<FileTypeChecker>::new();
+        let result = checker.check("\u{89}PNG\r\n\u{1a}\n\0\0\0\rIHDR");
+        assert!(result.is_identified);
+        assert_eq!(result.description, "PNG image (image/png)");
+    }
+
+    #[test]
+    fn test_check_pdf() {
+        let checker = Checker::<FileTypeChecker>::new();
+        let result = checker.check("%PDF-1.7\n%\u{e2}\u{e3}\u{cf}\u{d3}");
+        assert!(result.is_identified);
+        assert_eq!(result.description, "PDF document (application/pdf)");
+    }
+
+    #[test]
+    fn test_check_elf() {
+        let checker = Checker::<FileTypeChecker>::new();
+        let result = checker.check("\u{7f}ELF\u{2}\u{1}\u{1}");
+        assert!(result.is_identified);
+        assert_eq!(
+            result.description,
+            "ELF executable (application/x-executable)"
+        );
+    }
+
+    #[test]
+    fn test_check_plain_text_is_not_a_file() {
+        let checker = Checker::<FileTypeChecker>::new();
+        assert!(!checker.check("hello world").is_identified);
+        assert!(!checker.check("").is_identified);
+    }
+
+    #[test]
+    fn test_text_to_bytes_keeps_binary_files() {
+        assert_eq!(
+            text_to_bytes("\u{ff}\u{d8}\u{ff}\u{e0}"),
+            vec![0xff, 0xd8, 0xff, 0xe0]
+        );
+    }
+
+    #[test]
+    fn test_text_to_bytes_keeps_text_as_utf8() {
+        assert_eq!(text_to_bytes("café"), "café".as_bytes());
+    }
+}
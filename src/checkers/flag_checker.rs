@@ -0,0 +1,105 @@
+use lemmeknow::Identifier;
+use once_cell::sync::Lazy;
+
+use super::checker_type::{Check, Checker};
+use crate::{checkers::checker_result::CheckResult, config::get_config};
+use log::trace;
+use regex::Regex;
+
+/// The Flag Checker looks for CTF flags like `flag{...}` or `picoCTF{...}`.
+/// This is the struct for it.
+pub struct FlagChecker;
+
+/// Flag formats used by popular CTFs and platforms.
+/// Users can add their own event's prefix with `flag_prefixes` in the config.
+const COMMON_FLAG_PREFIXES: [&str; 10] = [
+    "flag", "ctf", "picoCTF", "HTB", "THM", "DUCTF", "CSCTF", "CHTB", "uiuctf", "actf",
+];
+
+/// Built once from the common prefixes and the ones in the config.
+/// Matches the prefix followed by `{...}` anywhere in the text, ignoring case.
+static FLAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    let config = get_config();
+    let prefixes: Vec<String> = COMMON_FLAG_PREFIXES
+        .iter()
+        .map(|prefix| prefix.to_string())
+        .chain(config.flag_prefixes.iter().cloned())
+        .map(|prefix| regex::escape(&prefix))
+        .collect();
+    Regex::new(&format!(
+        r"(?i)(?:{})\{{[^{{}}\r\n]+\}}",
+        prefixes.join("|")
+    ))
+    .expect("Flag prefixes are escaped, so the flag regex is always valid")
+});
+
+impl Check for Checker<FlagChecker> {
+    fn new() -> Self {
+        Checker {
+            name: "Flag Checker",
+            description: "Looks for CTF flags such as flag{...} or picoCTF{...}",
+            link: "https://ctftime.org/",
+            tags: vec!["ctf", "flag", "crib"],
+            expected_runtime: 0.01,
+            popularity: 1.0,
+            lemmeknow_config: Identifier::default(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn check(&self, text: &str) -> CheckResult {
+        trace!("Checking {} for a CTF flag", text);
+        let mut result = CheckResult::new(self);
+        result.text = text.to_string();
+
+        if let Some(flag) = FLAG_REGEX.find(text) {
+            result.is_identified = true;
+            // We report the flag on its own too, as that's what you submit
+            result.matched = Some(flag.as_str().to_string());
+            result.description = "CTF flag".to_string();
+            result.confidence = 1.0;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkers::{
+        checker_type::{Check, Checker},
+        flag_checker::FlagChecker,
+    };
+
+    #[test]
+    fn test_check_finds_flag() {
+        let checker = Checker::<FlagChecker>::new();
+        let result = checker.check("flag{this_is_the_flag}");
+        assert!(result.is_identified);
+        assert_eq!(result.matched.as_deref(), Some("flag{this_is_the_flag}"));
+    }
+
+    #[test]
+    fn test_check_extracts_flag_from_text() {
+        let checker = Checker::<FlagChecker>::new();
+        let result = checker.check("Well done! Your flag is picoCTF{r0t13_1s_n0t_3ncrypt10n} :)");
+        assert!(result.is_identified);
+        assert_eq!(
+            result.matched.as_deref(),
+            Some("picoCTF{r0t13_1s_n0t_3ncrypt10n}")
+        );
+    }
+
+    #[test]
+    fn test_check_ignores_case() {
+        let checker = Checker::<FlagChecker>::new();
+        assert!(checker.check("HTB{s0m3_fl4g}").is_identified);
+        assert!(checker.check("FLAG{upper}").is_identified);
+    }
+
+    #[test]
+    fn test_check_no_flag() {
+        let checker = Checker::<FlagChecker>::new();
+        assert!(!checker.check("flag is not here {nope}").is_identified);
+        assert!(!checker.check("flag{}").is_identified);
+    }
+}
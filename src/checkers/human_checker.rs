@@ -1,29 +1,216 @@
 use crate::checkers::checker_result::CheckResult;
-use crate::cli_pretty_printing::human_checker_check;
+use crate::cli_pretty_printing::{
+    human_checker_check, human_checker_quitting, human_checker_show_more,
+    human_checker_unknown_reply,
+};
 use crate::config::get_config;
-use crate::timer;
-use text_io::read;
+use log::warn;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::io::{stdin, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// What the human said about the possible plaintext
+#[derive(Debug, PartialEq, Eq)]
+pub enum HumanReply {
+    /// It's the plaintext, stop searching
+    Accept,
+    /// It's not the plaintext, keep searching
+    Reject,
+    /// This checker keeps getting it wrong, stop asking about it
+    RejectChecker,
+    /// Show the whole text and more about the checker
+    ShowMore,
+    /// Stop the search altogether
+    Quit,
+}
+
+impl HumanReply {
+    /// Reads a reply, an empty reply is a no
+    fn parse(reply: &str) -> Option<HumanReply> {
+        match reply.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => Some(HumanReply::Accept),
+            "" | "n" | "no" => Some(HumanReply::Reject),
+            "c" | "checker" => Some(HumanReply::RejectChecker),
+            "m" | "more" => Some(HumanReply::ShowMore),
+            "q" | "quit" => Some(HumanReply::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Decoders run in parallel, so only one of them can ask the human at a time
+static PROMPT_LOCK: Mutex<()> = Mutex::new(());
+
+/// What the human has told one search.
+/// Each search has its own, so searches running side by side (like the inputs
+/// of a batch) don't stop, pause or skip checkers for each other.
+#[derive(Debug, Default)]
+pub struct SearchState {
+    /// Checkers the human rejected everything from
+    rejected_checkers: Mutex<HashSet<&'static str>>,
+    /// Set when the human asks us to quit, the searcher stops when it sees this
+    quit: AtomicBool,
+    /// Set when the human accepts a plaintext, so nothing else in the search can replace it
+    accepted: AtomicBool,
+    /// The decoders which led to the text the decoders are now working on
+    path: Mutex<Vec<&'static str>>,
+    /// Set while the human is answering, so the search's timer doesn't count it
+    paused: Arc<AtomicBool>,
+}
+
+impl SearchState {
+    /// The flag the search's timer pauses on
+    pub fn paused(&self) -> Arc<AtomicBool> {
+        self.paused.clone()
+    }
+
+    /// Returns true if the human asked us to stop searching
+    pub fn quit_requested(&self) -> bool {
+        self.quit.load(Ordering::Relaxed)
+    }
+
+    /// The searcher calls this before decoding a text, with the decoders that produced it
+    pub fn set_path(&self, path: Vec<&'static str>) {
+        *self.path.lock().unwrap_or_else(|e| e.into_inner()) = path;
+    }
+
+    /// The decoders that made the text, like "Base64 → Caesar Cipher"
+    fn current_path(&self) -> String {
+        let mut path: Vec<String> = self
+            .path
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|decoder| decoder.to_string())
+            .collect();
+        CURRENT_DECODER.with(|decoder| path.extend(decoder.borrow().iter().cloned()));
+        if path.is_empty() {
+            "no decoders, it's the input".to_string()
+        } else {
+            path.join(" → ")
+        }
+    }
+
+    /// Returns true if the human rejected everything from this checker
+    fn rejected(&self, checker_name: &str) -> bool {
+        self.rejected_checkers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(checker_name)
+    }
+}
+
+thread_local! {
+    /// The decoder running on this thread, which made the text being checked
+    static CURRENT_DECODER: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// The search running on this thread, decoders are told it along with their name
+    static CURRENT_SEARCH: RefCell<Option<Arc<SearchState>>> = const { RefCell::new(None) };
+}
+
+/// The searcher calls this on its thread before it starts decoding
+pub fn set_current_search(search: Arc<SearchState>) {
+    CURRENT_SEARCH.with(|current| *current.borrow_mut() = Some(search));
+}
+
+/// The search running on this thread, so decoders started from it can be told
+pub fn current_search() -> Option<Arc<SearchState>> {
+    CURRENT_SEARCH.with(|current| current.borrow().clone())
+}
+
+/// Decoders call this before cracking so we can tell the human which decoder made the text,
+/// and answers go to the search which is decoding it
+pub fn set_current_decoder(name: &str, search: Option<Arc<SearchState>>) {
+    CURRENT_DECODER.with(|decoder| *decoder.borrow_mut() = Some(name.to_string()));
+    CURRENT_SEARCH.with(|current| *current.borrow_mut() = search);
+}
 
 /// The Human Checker asks humans if the expected plaintext is real plaintext
 /// We can use all the automated checkers in the world, but sometimes they get false positives
 /// Humans have the last say.
+/// If stdin isn't a terminal (say we're in a pipeline) nobody can answer, so we accept.
 /// TODO: Add a way to specify a list of checkers to use in the library. This checker is not library friendly!
-// compile this if we are not running tests
 pub fn human_checker(input: &CheckResult) -> bool {
-    timer::pause();
     // wait instead of get so it waits for config being set
     let config = get_config();
     // We still call human checker, just if config is false we return True
     if !config.human_checker_on || config.api_mode {
         return true;
     }
-    human_checker_check(&input.description, &input.text);
+    if !stdin().is_terminal() {
+        warn!("Stdin is not a terminal so the human checker can't ask, accepting the plaintext");
+        return true;
+    }
+
+    // Checks outside of a search (like a recipe) have nothing to remember
+    let search = current_search().unwrap_or_default();
+    let _prompt = PROMPT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    // Another decoder may have got an answer while we waited.
+    // Once the human has accepted a plaintext it's the only one, however confident the others are
+    if search.quit_requested()
+        || search.accepted.load(Ordering::Relaxed)
+        || search.rejected(input.checker_name)
+    {
+        return false;
+    }
 
-    let reply: String = read!("{}\n");
-    let result = reply.to_ascii_lowercase().starts_with('y');
-    if !result {
-        timer::resume();
+    search.paused.store(true, Ordering::Relaxed);
+    let path = search.current_path();
+    human_checker_check(&path, input);
+    loop {
+        let mut reply = String::new();
+        // Stdin closing means nobody is left to answer
+        let reply = match stdin().read_line(&mut reply) {
+            Ok(0) | Err(_) => HumanReply::Quit,
+            Ok(_) => match HumanReply::parse(&reply) {
+                Some(reply) => reply,
+                None => {
+                    human_checker_unknown_reply();
+                    continue;
+                }
+            },
+        };
+        match reply {
+            HumanReply::Accept => {
+                search.accepted.store(true, Ordering::Relaxed);
+                return true;
+            }
+            HumanReply::Reject => {}
+            HumanReply::RejectChecker => {
+                search
+                    .rejected_checkers
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(input.checker_name);
+            }
+            HumanReply::ShowMore => {
+                human_checker_show_more(&path, input);
+                continue;
+            }
+            HumanReply::Quit => {
+                search.quit.store(true, Ordering::Relaxed);
+                human_checker_quitting();
+            }
+        }
+        search.paused.store(false, Ordering::Relaxed);
         return false;
     }
-    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HumanReply;
+
+    #[test]
+    fn test_parse_replies() {
+        assert_eq!(HumanReply::parse("y\n"), Some(HumanReply::Accept));
+        assert_eq!(HumanReply::parse("YES"), Some(HumanReply::Accept));
+        assert_eq!(HumanReply::parse("\n"), Some(HumanReply::Reject));
+        assert_eq!(HumanReply::parse("no"), Some(HumanReply::Reject));
+        assert_eq!(HumanReply::parse("c"), Some(HumanReply::RejectChecker));
+        assert_eq!(HumanReply::parse("more"), Some(HumanReply::ShowMore));
+        assert_eq!(HumanReply::parse(" q "), Some(HumanReply::Quit));
+        assert_eq!(HumanReply::parse("maybe"), None);
+    }
 }
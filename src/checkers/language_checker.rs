@@ -0,0 +1,217 @@
+use crate::checkers::checker_result::CheckResult;
+use crate::config::get_config;
+use crate::storage::{LanguageProfile, LANGUAGES};
+use lemmeknow::Identifier;
+use log::{debug, trace};
+use std::collections::HashMap;
+
+use crate::checkers::checker_type::{Check, Checker};
+
+/// Checks for plaintext in languages other than English.
+/// The languages it looks for are set by `languages` in the config.
+pub struct LanguageChecker;
+
+/// If more than 40% of the words are common words of a language, we consider it that language.
+/// This is the same threshold the English checker uses.
+const PLAINTEXT_DETECTION_PERCENTAGE: f64 = 0.4;
+
+/// Short common words like "de" appear in several languages and in random decodings,
+/// so we want at least this many before we believe it.
+const MINIMUM_WORDS_FOUND: usize = 2;
+
+/// How much the common word ratio counts towards the score, the rest is letter frequency.
+const WORD_WEIGHT: f64 = 0.8;
+
+impl Check for Checker<LanguageChecker> {
+    fn new() -> Self {
+        Checker {
+            name: "Language Checker",
+            description:
+                "Checks for common words of French, German, Spanish, Portuguese and Russian",
+            link: "https://en.wikipedia.org/wiki/Letter_frequency",
+            tags: vec![
+                "language",
+                "french",
+                "german",
+                "spanish",
+                "portuguese",
+                "russian",
+            ],
+            expected_runtime: 0.1,
+            popularity: 0.5,
+            lemmeknow_config: Identifier::default(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn check(&self, text: &str) -> CheckResult {
+        trace!("Checking languages for {}", text);
+        let mut result = CheckResult {
+            is_identified: false,
+            text: text.to_string(),
+            checker_name: self.name,
+            checker_description: self.description,
+            description: "".to_string(),
+            link: self.link,
+            confidence: 0.0,
+            matched: None,
+        };
+
+        let input = normalise_for_language(text);
+        let words: Vec<&str> = input.split_whitespace().collect();
+        if words.is_empty() {
+            return result;
+        }
+
+        let config = get_config();
+        // The language which matched best, with how many words it found and its score
+        let mut best: Option<(&LanguageProfile, usize, f64)> = None;
+        for code in config.languages.iter() {
+            let profile = match LANGUAGES.get(code.as_str()) {
+                Some(profile) => profile,
+                // English and anything we don't have a profile for is handled elsewhere
+                None => continue,
+            };
+            let words_found = words
+                .iter()
+                .filter(|word| profile.words.contains(*word))
+                .count();
+            let word_ratio = words_found as f64 / words.len() as f64;
+            let score = WORD_WEIGHT * word_ratio
+                + (1.0 - WORD_WEIGHT) * letter_frequency_similarity(&input, profile);
+            trace!(
+                "{} has word ratio {} and score {}",
+                profile.name,
+                word_ratio,
+                score
+            );
+
+            let beats_best = match best {
+                Some((_, _, best_score)) => score > best_score,
+                None => true,
+            };
+            if word_ratio > PLAINTEXT_DETECTION_PERCENTAGE
+                && words_found >= MINIMUM_WORDS_FOUND
+                && beats_best
+            {
+                best = Some((profile, words_found, score));
+            }
+        }
+
+        if let Some((profile, words_found, score)) = best {
+            debug!("Found {} {} words in {}", words_found, profile.name, text);
+            result.is_identified = true;
+            result.description = format!("{} text", profile.name);
+            result.confidence = score.clamp(0.0, 1.0);
+        }
+        result
+    }
+}
+
+/// Lowercases the string and removes punctuation so we can look words up.
+/// Unlike `storage::normalise_string` it lowercases letters like "É" too,
+/// and apostrophes are kept for words like "c'est".
+fn normalise_for_language(input: &str) -> String {
+    input
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '\'')
+        .collect()
+}
+
+/// Cosine similarity between the letters of the text and the language's letter frequencies.
+/// 1.0 means the letters appear in exactly the expected proportions.
+fn letter_frequency_similarity(text: &str, profile: &LanguageProfile) -> f64 {
+    let mut counts: HashMap<char, f64> = HashMap::new();
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        *counts.entry(c).or_insert(0.0) += 1.0;
+    }
+    let dot: f64 = counts
+        .iter()
+        .map(|(c, count)| count * profile.letter_frequencies.get(c).unwrap_or(&0.0))
+        .sum();
+    let text_norm = counts.values().map(|x| x * x).sum::<f64>().sqrt();
+    let profile_norm = profile
+        .letter_frequencies
+        .values()
+        .map(|x| x * x)
+        .sum::<f64>()
+        .sqrt();
+    if text_norm == 0.0 || profile_norm == 0.0 {
+        return 0.0;
+    }
+    dot / (text_norm * profile_norm)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkers::{
+        checker_type::{Check, Checker},
+        language_checker::{letter_frequency_similarity, LanguageChecker},
+    };
+    use crate::storage::LANGUAGES;
+
+    #[test]
+    fn test_check_french() {
+        let checker = Checker::<LanguageChecker>::new();
+        let result = checker.check("Je ne sais pas où est la gare, mais elle est très loin.");
+        assert!(result.is_identified);
+        assert_eq!(result.description, "French text");
+    }
+
+    #[test]
+    fn test_check_german() {
+        let checker = Checker::<LanguageChecker>::new();
+        let result = checker.check("Ich habe heute keine Zeit, aber morgen kann ich kommen.");
+        assert!(result.is_identified);
+        assert_eq!(result.description, "German text");
+    }
+
+    #[test]
+    fn test_check_spanish() {
+        let checker = Checker::<LanguageChecker>::new();
+        let result = checker.check("¿Dónde está el hombre que vino ayer a la casa?");
+        assert!(result.is_identified);
+        assert_eq!(result.description, "Spanish text");
+    }
+
+    #[test]
+    fn test_check_portuguese() {
+        let checker = Checker::<LanguageChecker>::new();
+        let result = checker.check("Eu não sei onde ele está, mas ela disse que ele vem amanhã.");
+        assert!(result.is_identified);
+        assert_eq!(result.description, "Portuguese text");
+    }
+
+    #[test]
+    fn test_check_russian() {
+        let checker = Checker::<LanguageChecker>::new();
+        let result =
+            checker.check("Я не знаю, где он сейчас, но она сказала, что он будет завтра.");
+        assert!(result.is_identified);
+        assert_eq!(result.description, "Russian text");
+    }
+
+    #[test]
+    fn test_check_gibberish_fails() {
+        let checker = Checker::<LanguageChecker>::new();
+        assert!(!checker.check("vjkrerkdnxhrfjekfdjexk qzpw").is_identified);
+    }
+
+    #[test]
+    fn test_check_one_shared_word_fails() {
+        // "de" is French, Spanish and Portuguese, but one word isn't enough
+        let checker = Checker::<LanguageChecker>::new();
+        assert!(!checker.check("de xkcd").is_identified);
+    }
+
+    #[test]
+    fn test_letter_frequency_prefers_matching_alphabet() {
+        let russian = LANGUAGES.get("ru").unwrap();
+        let german = LANGUAGES.get("de").unwrap();
+        let text = "привет как дела";
+        assert!(
+            letter_frequency_similarity(text, russian) > letter_frequency_similarity(text, german)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use super::checker_type::{Check, Checker};
 use crate::checkers::checker_result::CheckResult;
-use lemmeknow::{Data, Identifier};
+use crate::config::get_config;
+use crate::decoders::punycode_decoder::encode_punycode_labels;
+use lemmeknow::{Data, Identifier, Match};
 
 /// The LemmeKnow Checker checks if the text matches a known Regex pattern.
 /// This is the struct for it.
@@ -8,6 +10,7 @@ pub struct LemmeKnow;
 
 impl Check for Checker<LemmeKnow> {
     fn new() -> Self {
+        let lemmeknow_config = &get_config().lemmeknow_config;
         Checker {
             // TODO: Update fields with proper values
             name: "LemmeKnow Checker",
@@ -16,18 +19,36 @@ impl Check for Checker<LemmeKnow> {
             tags: vec!["lemmeknow", "regex"],
             expected_runtime: 0.01,
             popularity: 1.0,
-            lemmeknow_config: Identifier::default().min_rarity(0.1),
+            lemmeknow_config: Identifier {
+                min_rarity: lemmeknow_config.min_rarity,
+                max_rarity: lemmeknow_config.max_rarity,
+                tags: lemmeknow_config.tags.clone(),
+                exclude_tags: lemmeknow_config.exclude_tags.clone(),
+                // We are checking decoded text, never a file name
+                file_support: false,
+                boundaryless: lemmeknow_config.boundaryless,
+            },
             _phantom: std::marker::PhantomData,
         }
     }
 
     fn check(&self, text: &str) -> CheckResult {
-        let lemmeknow_result = self.lemmeknow_config.identify(text);
+        let mut lemmeknow_result = identify(&self.lemmeknow_config, text);
+        // LemmeKnow only knows ASCII hostnames, so try internationalised ones in their xn-- form
+        if lemmeknow_result.is_empty() {
+            if let Some(ascii_text) = encode_punycode_labels(text) {
+                lemmeknow_result = identify(&self.lemmeknow_config, &ascii_text);
+            }
+        }
         let mut is_identified = false;
         let mut description = "".to_string();
+        let mut confidence = 0.0;
         if !lemmeknow_result.is_empty() {
             is_identified = true;
-            description = format_data_result(&lemmeknow_result[0].data)
+            description = format_data_result(&lemmeknow_result[0].data);
+            // Rarity is how unlikely the pattern is to match by accident,
+            // so a rare pattern like an IPv4 address is a confident match
+            confidence = f64::from(lemmeknow_result[0].data.rarity).clamp(0.0, 1.0);
         }
 
         CheckResult {
@@ -38,12 +59,101 @@ impl Check for Checker<LemmeKnow> {
             // Returns a vector of matches
             description,
             link: self.link,
+            confidence,
+            matched: None,
         }
     }
 }
 
+/// Runs LemmeKnow with the rarity and boundary settings, then applies the tags ourselves.
+/// LemmeKnow only keeps patterns which have every included tag, but we want any of them
+/// so that `--lemmeknow-tags Credentials,Networking` accepts both. Tags also ignore case.
+fn identify(config: &Identifier, text: &str) -> Vec<Match> {
+    let identifier = Identifier {
+        min_rarity: config.min_rarity,
+        max_rarity: config.max_rarity,
+        tags: vec![],
+        exclude_tags: vec![],
+        file_support: false,
+        boundaryless: config.boundaryless,
+    };
+    identifier
+        .identify(text)
+        .into_iter()
+        .filter(|found| {
+            let has_tag = |wanted: &String| {
+                found
+                    .data
+                    .tags
+                    .iter()
+                    .any(|tag| tag.eq_ignore_ascii_case(wanted))
+            };
+            (config.tags.is_empty() || config.tags.iter().any(has_tag))
+                && !config.exclude_tags.iter().any(has_tag)
+        })
+        .collect()
+}
+
 /// Formats the data result to a string
 /// This is used to display the result in the UI
 fn format_data_result(input: &Data) -> String {
     input.name.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::checkers::{
+        checker_type::{Check, Checker},
+        lemmeknow_checker::LemmeKnow,
+    };
+
+    #[test]
+    fn test_check_ip_address() {
+        let checker = Checker::<LemmeKnow>::new();
+        assert!(checker.check("192.168.0.1").is_identified);
+    }
+
+    #[test]
+    fn test_check_only_included_tags() {
+        let mut checker = Checker::<LemmeKnow>::new();
+        checker.lemmeknow_config.tags = vec!["Credentials".to_string()];
+        assert!(!checker.check("192.168.0.1").is_identified);
+    }
+
+    #[test]
+    fn test_check_any_included_tag_ignoring_case() {
+        let mut checker = Checker::<LemmeKnow>::new();
+        checker.lemmeknow_config.tags = vec!["Credentials".to_string(), "networking".to_string()];
+        assert!(checker.check("192.168.0.1").is_identified);
+    }
+
+    #[test]
+    fn test_check_excluded_tags() {
+        let mut checker = Checker::<LemmeKnow>::new();
+        checker.lemmeknow_config.exclude_tags = vec!["IPv4".to_string()];
+        assert!(!checker.check("192.168.0.1").is_identified);
+    }
+
+    #[test]
+    fn test_check_max_rarity() {
+        let mut checker = Checker::<LemmeKnow>::new();
+        checker.lemmeknow_config.max_rarity = 0.0;
+        assert!(!checker.check("192.168.0.1").is_identified);
+    }
+
+    #[test]
+    fn test_check_boundaryless() {
+        let mut checker = Checker::<LemmeKnow>::new();
+        assert!(
+            !checker
+                .check("the server is at 192.168.0.1 today")
+                .is_identified
+        );
+        checker.lemmeknow_config.boundaryless = true;
+        assert!(
+            checker
+                .check("the server is at 192.168.0.1 today")
+                .is_identified
+        );
+    }
+}
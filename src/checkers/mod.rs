@@ -2,27 +2,47 @@ use self::{
     athena::Athena,
     checker_result::CheckResult,
     checker_type::{Check, Checker},
+    crib_checker::CribChecker,
     english::EnglishChecker,
+    file_type_checker::FileTypeChecker,
+    flag_checker::FlagChecker,
+    language_checker::LanguageChecker,
     lemmeknow_checker::LemmeKnow,
+    quadgram_checker::QuadgramChecker,
     regex_checker::RegexChecker,
+    structured_data_checker::StructuredDataChecker,
 };
 
 /// The default checker we use which simply calls all other checkers in order.
 pub mod athena;
+/// Checker expressions combine checkers with AND, OR and NOT
+pub mod checker_expression;
 /// The checkerResult struct is used to store the results of a checker.
 pub mod checker_result;
 /// This is the base checker that all other checkers inherit from.
 pub mod checker_type;
+/// The Crib Checker looks for known pieces of the plaintext
+pub mod crib_checker;
 /// The default checker we use which simply calls all other checkers in order.
 pub mod default_checker;
 /// The English Checker is a checker that checks if the input is English
 pub mod english;
+/// The File Type Checker recognises binary files from their magic bytes
+pub mod file_type_checker;
+/// The Flag Checker looks for CTF flags like flag{...}
+pub mod flag_checker;
 /// The Human Checker asks humans if the expected plaintext is real plaintext
 pub mod human_checker;
+/// The Language Checker checks for plaintext in languages other than English
+pub mod language_checker;
 /// The LemmeKnow Checker checks if the text matches a known Regex pattern.
 pub mod lemmeknow_checker;
+/// The Quadgram Checker scores how English-like text is, even without spaces
+pub mod quadgram_checker;
 /// The Regex checker checks to see if the intended text matches the plaintext
 pub mod regex_checker;
+/// The Structured Data checker accepts well-formed JSON, XML, YAML and CSV
+pub mod structured_data_checker;
 
 /// CheckerTypes is a wrapper enum for Checker
 pub enum CheckerTypes {
@@ -34,6 +54,18 @@ pub enum CheckerTypes {
     CheckAthena(Checker<Athena>),
     /// Wrapper for Regex
     CheckRegex(Checker<RegexChecker>),
+    /// Wrapper for Quadgram Checker
+    CheckQuadgram(Checker<QuadgramChecker>),
+    /// Wrapper for Language Checker
+    CheckLanguage(Checker<LanguageChecker>),
+    /// Wrapper for Flag Checker
+    CheckFlag(Checker<FlagChecker>),
+    /// Wrapper for File Type Checker
+    CheckFileType(Checker<FileTypeChecker>),
+    /// Wrapper for Structured Data Checker
+    CheckStructuredData(Checker<StructuredDataChecker>),
+    /// Wrapper for Crib Checker
+    CheckCrib(Checker<CribChecker>),
 }
 
 impl CheckerTypes {
@@ -44,6 +76,14 @@ impl CheckerTypes {
             CheckerTypes::CheckEnglish(english_checker) => english_checker.check(text),
             CheckerTypes::CheckAthena(athena_checker) => athena_checker.check(text),
             CheckerTypes::CheckRegex(regex_checker) => regex_checker.check(text),
+            CheckerTypes::CheckQuadgram(quadgram_checker) => quadgram_checker.check(text),
+            CheckerTypes::CheckLanguage(language_checker) => language_checker.check(text),
+            CheckerTypes::CheckFlag(flag_checker) => flag_checker.check(text),
+            CheckerTypes::CheckFileType(file_type_checker) => file_type_checker.check(text),
+            CheckerTypes::CheckStructuredData(structured_data_checker) => {
+                structured_data_checker.check(text)
+            }
+            CheckerTypes::CheckCrib(crib_checker) => crib_checker.check(text),
         }
     }
 }
@@ -63,9 +103,44 @@ mod tests {
         assert!(athena.check("192.168.0.1").is_identified);
     }
 
+    #[test]
+    fn test_check_reports_confidence() {
+        let athena = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let result = athena.check("192.168.0.1");
+        assert!(result.confidence > 0.0 && result.confidence <= 1.0);
+    }
+
+    #[test]
+    fn test_check_finds_flag_before_lemmeknow() {
+        // LemmeKnow would call this a URL, but we want the flag
+        let athena = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let result = athena.check("https://example.com/?ctf{w3b_fl4g}");
+        assert!(result.is_identified);
+        assert_eq!(result.checker_name, "Flag Checker");
+        assert_eq!(result.matched.as_deref(), Some("ctf{w3b_fl4g}"));
+    }
+
     #[test]
     fn test_check_goes_to_dictionary() {
         let athena = CheckerTypes::CheckAthena(Checker::<Athena>::new());
         assert!(athena.check("and").is_identified);
     }
+
+    #[test]
+    fn test_check_goes_to_other_languages() {
+        let athena = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let result = athena.check("Das ist nicht mein Haus, aber ich wohne hier.");
+        assert!(result.is_identified);
+        assert_eq!(result.description, "German text");
+    }
+
+    #[test]
+    fn test_check_goes_to_quadgrams() {
+        let athena = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        assert!(
+            athena
+                .check("meetmeattheusualplaceattenratherthaneightoclock")
+                .is_identified
+        );
+    }
 }
@@ -0,0 +1,179 @@
+use crate::checkers::checker_result::CheckResult;
+use crate::storage::ENGLISH_QUADGRAMS;
+use lemmeknow::Identifier;
+use log::{debug, trace};
+
+use crate::checkers::checker_type::{Check, Checker};
+
+/// Scores text by how likely its quadgrams (4 letter sequences) are in English.
+/// Unlike the dictionary checker this does not need spaces between words.
+pub struct QuadgramChecker;
+
+/// The average log10 probability of a quadgram in normal English text.
+/// A fitness of 1.0 means the text is as English-like as typical prose.
+const ENGLISH_AVERAGE_LOG_PROBABILITY: f64 = -4.0;
+
+/// Texts with fewer letters than this don't have enough quadgrams to judge.
+const MINIMUM_LETTERS: usize = 8;
+
+/// Texts with at least this many letters can use the lower fitness threshold.
+const LONG_TEXT_LETTERS: usize = 20;
+
+/// Fitness needed to call a long text English.
+/// Random letters and wrong caesar shifts stay well under this once there are 20 letters.
+const LONG_TEXT_FITNESS_THRESHOLD: f64 = 0.55;
+
+/// Short texts are noisier, so they need a higher fitness.
+const SHORT_TEXT_FITNESS_THRESHOLD: f64 = 0.7;
+
+impl Check for Checker<QuadgramChecker> {
+    fn new() -> Self {
+        Checker {
+            name: "Quadgram Checker",
+            description: "Scores text using the log probability of English quadgrams",
+            link: "http://practicalcryptography.com/cryptanalysis/text-characterisation/quadgrams/",
+            tags: vec!["english", "ngram", "quadgram"],
+            expected_runtime: 0.01,
+            popularity: 0.9,
+            lemmeknow_config: Identifier::default(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn check(&self, text: &str) -> CheckResult {
+        trace!("Checking quadgram fitness of {}", text);
+        let mut result = CheckResult {
+            is_identified: false,
+            text: text.to_string(),
+            checker_name: self.name,
+            checker_description: self.description,
+            description: "English quadgrams".to_string(),
+            link: self.link,
+            confidence: 0.0,
+            matched: None,
+        };
+
+        let fitness = match quadgram_fitness(text) {
+            Some(fitness) => fitness,
+            None => return result,
+        };
+        let letters = text.chars().filter(char::is_ascii_alphabetic).count();
+        let threshold = if letters >= LONG_TEXT_LETTERS {
+            LONG_TEXT_FITNESS_THRESHOLD
+        } else {
+            SHORT_TEXT_FITNESS_THRESHOLD
+        };
+
+        result.description = format!("English quadgrams (fitness {fitness:.2})");
+        result.confidence = fitness;
+        if fitness >= threshold {
+            debug!(
+                "Quadgram fitness {} identified {} as English",
+                fitness, text
+            );
+            result.is_identified = true;
+        }
+        result
+    }
+}
+
+/// Returns how English-like the letters of the text are, from 0 (random) to 1 (typical English).
+/// Returns None if there are too few letters, or if letters are not the majority of
+/// the text (hex, base64 and so on aren't English no matter what their letters say).
+pub fn quadgram_fitness(text: &str) -> Option<f64> {
+    let letters: Vec<u8> = text
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let visible = text.chars().filter(|c| !c.is_whitespace()).count();
+    if letters.len() < MINIMUM_LETTERS || letters.len() * 2 < visible {
+        return None;
+    }
+
+    let table = &*ENGLISH_QUADGRAMS;
+    let quadgrams = letters.windows(4);
+    let count = quadgrams.len() as f64;
+    let total: f64 = quadgrams
+        .map(|quadgram| {
+            // Only ASCII letters are left, so this is always valid UTF-8
+            let quadgram = std::str::from_utf8(quadgram).unwrap_or_default();
+            *table
+                .log_probabilities
+                .get(quadgram)
+                .unwrap_or(&table.floor)
+        })
+        .sum();
+    let average = total / count;
+
+    let fitness = (average - table.floor) / (ENGLISH_AVERAGE_LOG_PROBABILITY - table.floor);
+    Some(fitness.clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkers::{
+        checker_type::{Check, Checker},
+        quadgram_checker::{quadgram_fitness, QuadgramChecker},
+    };
+
+    #[test]
+    fn test_check_unspaced_english() {
+        let checker = Checker::<QuadgramChecker>::new();
+        assert!(
+            checker
+                .check("thequickbrownfoxjumpsoverthelazydog")
+                .is_identified
+        );
+    }
+
+    #[test]
+    fn test_check_spaced_english() {
+        let checker = Checker::<QuadgramChecker>::new();
+        assert!(
+            checker
+                .check("This is a sentence that should read as English.")
+                .is_identified
+        );
+    }
+
+    #[test]
+    fn test_check_caesar_shifted_english_fails() {
+        let checker = Checker::<QuadgramChecker>::new();
+        assert!(
+            !checker
+                .check("Guvf vf n fragrapr gung fubhyq ernq nf Ratyvfu.")
+                .is_identified
+        );
+    }
+
+    #[test]
+    fn test_check_random_letters_fails() {
+        let checker = Checker::<QuadgramChecker>::new();
+        assert!(!checker.check("qxzjvkwpqzmxbvtrlqpw").is_identified);
+    }
+
+    #[test]
+    fn test_check_base64_fails() {
+        let checker = Checker::<QuadgramChecker>::new();
+        assert!(!checker.check("aGVsbG8gd29ybGQ=").is_identified);
+    }
+
+    #[test]
+    fn test_check_too_short() {
+        assert!(quadgram_fitness("the").is_none());
+        assert!(quadgram_fitness("").is_none());
+    }
+
+    #[test]
+    fn test_check_mostly_digits_has_no_fitness() {
+        assert!(quadgram_fitness("1234567890 1234567890 the one").is_none());
+    }
+
+    #[test]
+    fn test_fitness_orders_english_above_gibberish() {
+        let english = quadgram_fitness("attackatdawnwiththewholearmy").unwrap();
+        let gibberish = quadgram_fitness("nggnpxngqnjajvgugurjubyrnezl").unwrap();
+        assert!(english > gibberish);
+    }
+}
@@ -27,8 +27,15 @@ impl Check for Checker<RegexChecker> {
         trace!("Checking {} with regex", text);
         // TODO put this into a lazy static so we don't generate it everytime
         let config = get_config();
-        let regex_to_parse = config.regex.clone();
-        let re = Regex::new(&regex_to_parse.unwrap()).unwrap();
+        // `Config::validate` rejects bad regexes before we search,
+        // so this only happens if the library is used without it
+        let Some(re) = config
+            .regex
+            .as_deref()
+            .and_then(|regex| Regex::new(regex).ok())
+        else {
+            return CheckResult::new(self);
+        };
 
         let regex_check_result = re.is_match(text);
         let mut plaintext_found = false;
@@ -44,6 +51,9 @@ impl Check for Checker<RegexChecker> {
             checker_description: self.description,
             description: printed_name,
             link: self.link,
+            // A regex either matches or it doesn't
+            confidence: if plaintext_found { 1.0 } else { 0.0 },
+            matched: None,
         }
     }
 }
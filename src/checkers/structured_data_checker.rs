@@ -0,0 +1,457 @@
+use lemmeknow::Identifier;
+use serde_json::Value;
+
+use super::checker_type::{Check, Checker};
+use crate::checkers::{checker_result::CheckResult, quadgram_checker::quadgram_fitness};
+use log::trace;
+
+/// The Structured Data Checker accepts well-formed JSON, XML, YAML and CSV documents.
+/// Config blobs have very few dictionary words, so the English checker would reject them.
+/// Caesar shifts of a document are usually still well-formed, so the words in it
+/// (keys, values and tag names) have to read like English too.
+pub struct StructuredDataChecker;
+
+/// We don't try to parse anything bigger than this, it takes too long
+/// and huge blobs are more likely to be noise than a config file.
+const MAX_DOCUMENT_BYTES: usize = 1_000_000;
+
+/// Anything shorter than this is too small to call a document.
+const MIN_DOCUMENT_BYTES: usize = 8;
+
+/// YAML needs at least this many lines before we believe it.
+const MIN_LINES: usize = 2;
+
+/// CSV needs a header and at least two records, two lines with the same
+/// number of commas is just as likely to be prose.
+const MIN_CSV_ROWS: usize = 3;
+
+/// How English-like the words in a document must be, as a quadgram fitness.
+/// Keys like `db_host` are abbreviated so this is lower than the Quadgram Checker's,
+/// but caesar shifted words stay well under it.
+const MIN_WORD_FITNESS: f64 = 0.4;
+
+/// The delimiters we try when looking for CSV.
+const CSV_DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+
+impl Check for Checker<StructuredDataChecker> {
+    fn new() -> Self {
+        Checker {
+            name: "Structured Data Checker",
+            description: "Checks if the text is a well-formed JSON, XML, YAML or CSV document",
+            link: "https://en.wikipedia.org/wiki/Serialization#Serialization_formats",
+            tags: vec!["json", "xml", "yaml", "csv", "config"],
+            expected_runtime: 0.05,
+            popularity: 0.6,
+            lemmeknow_config: Identifier::default(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn check(&self, text: &str) -> CheckResult {
+        trace!("Checking if {} is structured data", text);
+        let mut result = CheckResult::new(self);
+        result.text = text.to_string();
+
+        let Some(format) = detect_format(text) else {
+            return result;
+        };
+        // Any caesar shift of a document is still a valid document,
+        // so we are as confident as its words are English
+        let Some(fitness) = word_fitness(text) else {
+            return result;
+        };
+        result.description = format!("{format} document (word fitness {fitness:.2})");
+        result.confidence = fitness;
+        result.is_identified = fitness >= MIN_WORD_FITNESS;
+        result
+    }
+}
+
+/// How English-like the words in the document are, using the letters outside
+/// its punctuation. Returns None if there are too few letters to tell.
+fn word_fitness(text: &str) -> Option<f64> {
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| !word.is_empty())
+        .collect();
+    quadgram_fitness(&words.join(" "))
+}
+
+/// Returns the name of the format the text is written in, if any.
+/// Text with control characters is never a document, which rules out most binary.
+fn detect_format(text: &str) -> Option<&'static str> {
+    let trimmed = text.trim();
+    if trimmed.len() < MIN_DOCUMENT_BYTES
+        || trimmed.len() > MAX_DOCUMENT_BYTES
+        || trimmed
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+    {
+        return None;
+    }
+
+    if is_json(trimmed) {
+        Some("JSON")
+    } else if is_xml(trimmed).is_some() {
+        Some("XML")
+    } else if is_yaml(trimmed) {
+        Some("YAML")
+    } else if is_csv(trimmed) {
+        Some("CSV")
+    } else {
+        None
+    }
+}
+
+/// A JSON document has to parse as a non-empty object or array,
+/// `"hello"` or `42` on their own are not documents.
+fn is_json(text: &str) -> bool {
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Object(object)) => !object.is_empty(),
+        Ok(Value::Array(array)) => !array.is_empty(),
+        _ => false,
+    }
+}
+
+/// Checks the text is well-formed XML: one root element, every tag closed in the
+/// right order and attributes quoted. We don't check entities or DTDs.
+fn is_xml(text: &str) -> Option<()> {
+    if !text.starts_with('<') || !text.ends_with('>') {
+        return None;
+    }
+    let mut open_tags: Vec<&str> = Vec::new();
+    let mut seen_root = false;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<?") {
+            rest = skip_past(after, "?>")?;
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = skip_past(after, "-->")?;
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            if open_tags.is_empty() {
+                return None;
+            }
+            rest = skip_past(after, "]]>")?;
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            // A DOCTYPE, which may have an internal subset in brackets
+            let end = after.find('>')?;
+            rest = match after[..end].find('[') {
+                Some(_) => skip_past(after, "]>")?,
+                None => &after[end + 1..],
+            };
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>')?;
+            if open_tags.pop()? != after[..end].trim_end() {
+                return None;
+            }
+            seen_root = open_tags.is_empty();
+            rest = &after[end + 1..];
+        } else if let Some(after) = rest.strip_prefix('<') {
+            let end = tag_end(after)?;
+            let tag = &after[..end];
+            let (tag, self_closing) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            let name = &tag[..name_end];
+            // Only one root element is allowed
+            if !is_xml_name(name) || !has_valid_attributes(&tag[name_end..]) || seen_root {
+                return None;
+            }
+            if self_closing {
+                seen_root = open_tags.is_empty();
+            } else {
+                open_tags.push(name);
+            }
+            rest = &after[end + 1..];
+        } else {
+            // Text between tags, only whitespace is allowed outside the root
+            let end = rest.find('<').unwrap_or(rest.len());
+            if open_tags.is_empty() && !rest[..end].trim().is_empty() {
+                return None;
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    if open_tags.is_empty() && seen_root {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// Returns what's left of the text after the next `marker`
+fn skip_past<'a>(text: &'a str, marker: &str) -> Option<&'a str> {
+    let start = text.find(marker)?;
+    Some(&text[start + marker.len()..])
+}
+
+/// Finds the `>` that ends a tag, ignoring any inside quoted attribute values
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '>') => return Some(i),
+            (None, '<') => return None,
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// XML names start with a letter, `_` or `:` and then carry on with those, digits, `-` or `.`
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '-' | '.'))
+}
+
+/// Attributes must all look like `name="value"` or `name='value'`
+fn has_valid_attributes(mut attributes: &str) -> bool {
+    loop {
+        attributes = attributes.trim_start();
+        if attributes.is_empty() {
+            return true;
+        }
+        let Some((name, value)) = attributes.split_once('=') else {
+            return false;
+        };
+        let value = value.trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            return false;
+        };
+        let Some(end) = value[1..].find(quote) else {
+            return false;
+        };
+        if !is_xml_name(name.trim()) {
+            return false;
+        }
+        attributes = &value[end + 2..];
+    }
+}
+
+/// YAML is too flexible to parse without a library, so we accept the shape config files have:
+/// every line is a `key: value` mapping, a `- item` list entry, a comment or a document marker,
+/// indented with spaces, with at least one mapping.
+fn is_yaml(text: &str) -> bool {
+    let mut content_lines = 0;
+    let mut mappings = 0;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" || trimmed == "..." {
+            continue;
+        }
+        // YAML doesn't allow tabs for indentation
+        if line.starts_with('\t') {
+            return false;
+        }
+        content_lines += 1;
+        let list_item = trimmed
+            .strip_prefix("- ")
+            .or_else(|| (trimmed == "-").then_some(""));
+        if is_yaml_mapping(list_item.unwrap_or(trimmed)) {
+            mappings += 1;
+        } else if list_item.is_none() {
+            return false;
+        }
+    }
+    content_lines >= MIN_LINES && mappings > 0
+}
+
+/// A YAML mapping is `key:` or `key: value`, where the key is a simple word or quoted
+fn is_yaml_mapping(line: &str) -> bool {
+    let Some((key, value)) = line.split_once(':') else {
+        return false;
+    };
+    let quoted = key.len() >= 2
+        && ((key.starts_with('"') && key.ends_with('"'))
+            || (key.starts_with('\'') && key.ends_with('\'')));
+    let simple = !key.is_empty()
+        && !key.ends_with(' ')
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '));
+    (quoted || simple) && (value.is_empty() || value.starts_with(' '))
+}
+
+/// CSV needs a header and at least two records, which all have the same number
+/// (two or more) of columns. The header has to look like one: distinct names, not numbers.
+fn is_csv(text: &str) -> bool {
+    let rows: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if rows.len() < MIN_CSV_ROWS {
+        return false;
+    }
+    CSV_DELIMITERS.iter().any(|delimiter| {
+        let columns = csv_columns(rows[0], *delimiter);
+        columns.is_some_and(|columns| columns >= 2)
+            && rows
+                .iter()
+                .all(|row| csv_columns(row, *delimiter) == columns)
+            && is_csv_header(rows[0], *delimiter)
+    })
+}
+
+/// A header names its columns, so every field is a short, distinct name which isn't a number
+fn is_csv_header(row: &str, delimiter: char) -> bool {
+    /// Longer than this and it's a sentence, not a column name
+    const MAX_HEADER_WORDS: usize = 4;
+
+    let fields: Vec<&str> = row
+        .split(delimiter)
+        .map(|field| field.trim().trim_matches('"').trim())
+        .collect();
+    let mut names: Vec<String> = fields.iter().map(|field| field.to_lowercase()).collect();
+    names.sort();
+    names.dedup();
+    names.len() == fields.len()
+        && fields.iter().all(|field| {
+            field.chars().any(char::is_alphabetic)
+                && field.parse::<f64>().is_err()
+                && field.split_whitespace().count() <= MAX_HEADER_WORDS
+        })
+}
+
+/// Counts the columns of a CSV row, respecting quoted fields.
+/// Returns None if a quote is never closed.
+fn csv_columns(row: &str, delimiter: char) -> Option<usize> {
+    let mut columns = 1;
+    let mut in_quotes = false;
+    for c in row.chars() {
+        if c == '"' {
+            // An escaped "" inside quotes toggles twice, so it works out
+            in_quotes = !in_quotes;
+        } else if c == delimiter && !in_quotes {
+            columns += 1;
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    Some(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkers::{
+        checker_type::{Check, Checker},
+        structured_data_checker::{detect_format, StructuredDataChecker},
+    };
+
+    #[test]
+    fn test_check_json() {
+        let checker = Checker::<StructuredDataChecker>::new();
+        let result = checker.check(r#"{"db_host": "10.0.0.5", "port": 5432, "tls": true}"#);
+        assert!(result.is_identified);
+        assert!(result.description.starts_with("JSON document"));
+    }
+
+    #[test]
+    fn test_check_caesar_shifted_json_is_not_plaintext() {
+        let checker = Checker::<StructuredDataChecker>::new();
+        // ROT13 of a config file, which is still valid JSON
+        let rot13 = r#"{"qngnonfr": "cebqhpgvba", "hfreanzr": "nqzva", "gvzrbhg": 30}"#;
+        assert_eq!(detect_format(rot13), Some("JSON"));
+        assert!(!checker.check(rot13).is_identified);
+        assert!(
+            checker
+                .check(r#"{"database": "production", "username": "admin", "timeout": 30}"#)
+                .is_identified
+        );
+    }
+
+    #[test]
+    fn test_check_json_scalar_is_not_a_document() {
+        assert_eq!(detect_format("\"just a string\""), None);
+        assert_eq!(detect_format("12345678"), None);
+    }
+
+    #[test]
+    fn test_check_xml() {
+        let xml = "<?xml version=\"1.0\"?>\n<!-- settings -->\n<config env='prod'>\n  <user id=\"7\">admin</user>\n  <enabled/>\n  <![CDATA[<raw>]]>\n</config>";
+        assert_eq!(detect_format(xml), Some("XML"));
+    }
+
+    #[test]
+    fn test_check_xml_mismatched_tags() {
+        assert_eq!(detect_format("<config><user></config></user>"), None);
+        assert_eq!(detect_format("<a>one</a><b>two</b>"), None);
+        assert_eq!(detect_format("<config attr=unquoted></config>"), None);
+    }
+
+    #[test]
+    fn test_check_yaml() {
+        let yaml = "---\n# deploy settings\nname: backend\nreplicas: 3\nports:\n  - 80\n  - 443\nenv:\n  - name: DEBUG\n    value: \"false\"";
+        assert_eq!(detect_format(yaml), Some("YAML"));
+    }
+
+    #[test]
+    fn test_check_prose_is_not_yaml() {
+        assert_eq!(
+            detect_format("Note: this is a sentence.\nIt carries on here without a key"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_check_csv() {
+        let csv = "id,name,email\n1,\"Smith, John\",john@example.com\n2,Jane,jane@example.com";
+        assert_eq!(detect_format(csv), Some("CSV"));
+    }
+
+    #[test]
+    fn test_check_tab_separated() {
+        assert_eq!(detect_format("id\tname\n1\tjohn\n2\tjane"), Some("CSV"));
+        assert_eq!(detect_format("id\tname\n1\tjohn"), None);
+    }
+
+    #[test]
+    fn test_check_csv_needs_a_header_and_records() {
+        // Two lines of prose with the same number of commas
+        assert_eq!(
+            detect_format("Well, I went home, then slept\nSo, we ate lunch, then left"),
+            None
+        );
+        // A header which is really a record
+        assert_eq!(detect_format("1,2,3\n4,5,6\n7,8,9"), None);
+        assert_eq!(detect_format("id,name\n1,john"), None);
+    }
+
+    #[test]
+    fn test_check_caesar_shifted_csv_is_not_plaintext() {
+        let checker = Checker::<StructuredDataChecker>::new();
+        let csv = "name,email,country\njohn,john@example.com,england\njane,jane@example.com,wales";
+        let rot13 =
+            "anzr,rznvy,pbhagel\nwbua,wbua@rknzcyr.pbz,ratynaq\nwnar,wnar@rknzcyr.pbz,jnyrf";
+        assert!(checker.check(csv).is_identified);
+        assert!(!checker.check(rot13).is_identified);
+    }
+
+    #[test]
+    fn test_check_csv_uneven_rows() {
+        assert_eq!(detect_format("a,b,c\n1,2\n3,4,5,6"), None);
+    }
+
+    #[test]
+    fn test_check_size_limits() {
+        assert_eq!(detect_format("[1]"), None);
+        let huge = format!("[{}1]", "1,".repeat(600_000));
+        assert_eq!(detect_format(&huge), None);
+    }
+
+    #[test]
+    fn test_check_plain_text_and_binary() {
+        let checker = Checker::<StructuredDataChecker>::new();
+        assert!(!checker.check("hello my name is bee").is_identified);
+        assert!(!checker.check("{\"a\u{0}\": 1}").is_identified);
+    }
+}
@@ -1,15 +1,32 @@
-use std::{fs::File, io::Read};
+use std::{
+    fs::File,
+    io::{stdin, IsTerminal, Read},
+    path::{Path, PathBuf},
+};
 
-use crate::{cli_pretty_printing::panic_failure_both_input_and_fail_provided, config::Config};
+use crate::{
+    checkers::{checker_expression::CheckerExpression, crib_checker::Crib},
+    config::{
+        file::{read_config_files, resolve_settings, Settings},
+        Config, LEMMEKNOW_DEFAULT_CONFIG,
+    },
+    encoders::EncoderChain,
+    error::Error,
+    recipe::Recipe,
+    registry::ComponentKind,
+    storage::load_wordlists,
+    DecoderResult,
+};
 /// This doc string acts as a help message when the uses run '--help' in CLI mode
 /// as do all doc strings on fields
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use lemmeknow::Identifier;
 use log::trace;
 
 /// The struct for Clap CLI arguments
 #[derive(Parser)]
 #[command(author = "Bee <bee@skerritt.blog>", about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Opts {
     /// Some input. Because this isn't an Option<T> it's required to be used
     #[arg(short, long)]
@@ -20,8 +37,11 @@ pub struct Opts {
     verbose: u8,
 
     /// Turn off human checker, perfect for APIs where you don't want input from humans
-    #[arg(short, long)]
+    #[arg(short, long, overrides_with = "enable_human_checker")]
     disable_human_checker: bool,
+    /// Turn the human checker back on when a config file or profile turned it off
+    #[arg(long, overrides_with = "disable_human_checker")]
+    enable_human_checker: bool,
 
     /// Set timeout, if it is not decrypted after this time, it will return an error.
     /// Default is 5 seconds.
@@ -34,24 +54,194 @@ pub struct Opts {
     #[arg(short, long)]
     api_mode: Option<bool>,
     /// Opens a file for decoding
-    /// Use instead of `--text`
+    /// Use instead of `--text`. With no `--text` or `--file` the input is read from stdin
     #[arg(short, long)]
     file: Option<String>,
+    /// Decode every line of the input as its own text, or every file if `--file` is a directory.
+    /// The inputs are decoded in parallel and each one prints a line of JSON with its result
+    #[arg(long, conflicts_with = "output")]
+    batch: bool,
     /// If you have a crib (you know a piece of information in the plaintext)
     /// Or you want to create a custom regex to check against, you can use the Regex checker below.
     /// This turns off other checkers (English, LemmeKnow)
     #[arg(short, long)]
     regex: Option<String>,
+    /// How confident (0.0 to 1.0) a checker must be before we accept the plaintext.
+    /// Raise this if you are getting false positives. Default is 0.0
+    #[arg(long)]
+    confidence_threshold: Option<f64>,
+    /// Comma separated languages to look for plaintext in, for example `en,fr,de`.
+    /// Supported languages are en, fr, de, es, pt and ru. Default is all of them
+    #[arg(long, value_delimiter = ',', value_parser = ["en", "fr", "de", "es", "pt", "ru"])]
+    languages: Option<Vec<String>>,
+    /// Load your own wordlist so its words count as plaintext, for example project names.
+    /// Takes a file or a directory of files, and can be used multiple times
+    #[arg(long = "wordlist")]
+    wordlists: Vec<String>,
+    /// The flag format of the CTF you are playing, for example `--flag-prefix myctf` for `myctf{...}`.
+    /// Common formats like flag{...}, CTF{...} and picoCTF{...} are always checked. Can be used multiple times
+    #[arg(long = "flag-prefix")]
+    flag_prefixes: Vec<String>,
+    /// Write the decoded result to this file instead of printing it.
+    /// Useful when the answer is a file such as a PNG or ZIP
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Only accept LemmeKnow matches at least this rare (0.0 to 1.0).
+    /// Common patterns like phone numbers are not rare. Default is 0.1
+    #[arg(long)]
+    min_rarity: Option<f32>,
+    /// Only accept LemmeKnow matches at most this rare (0.0 to 1.0). Default is 1.0
+    #[arg(long)]
+    max_rarity: Option<f32>,
+    /// Only accept LemmeKnow matches with one of these comma separated tags,
+    /// for example `Credentials,Networking`
+    #[arg(long, value_delimiter = ',')]
+    lemmeknow_tags: Vec<String>,
+    /// Ignore LemmeKnow matches with any of these comma separated tags, for example `Phone`
+    #[arg(long, value_delimiter = ',')]
+    lemmeknow_exclude_tags: Vec<String>,
+    /// Let LemmeKnow find patterns anywhere in the text, instead of only when the whole text matches
+    #[arg(long, overrides_with = "no_boundaryless")]
+    boundaryless: bool,
+    /// Only let LemmeKnow match the whole text, when a config file or profile set `boundaryless`
+    #[arg(long, overrides_with = "boundaryless")]
+    no_boundaryless: bool,
+    /// Which checkers decide what is plaintext, combined with AND, OR, NOT and brackets.
+    /// For example `regex AND english` or `lemmeknow OR flag`. Put `>=0.8` after a checker for its own threshold.
+    /// Checkers are regex, crib, flag, file_type, lemmeknow, structured_data, english, language and quadgram
+    #[arg(long)]
+    checkers: Option<CheckerExpression>,
+    /// A piece of the plaintext you know, matched ignoring case, whitespace and punctuation.
+    /// Prefix with `start:`, `end:` or a character offset like `12:` to say where it is.
+    /// Can be used multiple times, and turns off the other checkers like `--regex` does
+    #[arg(long = "crib")]
+    cribs: Vec<Crib>,
+    /// Decode with a chain of decoders you already know instead of searching, like `base64,caesar:13,hex`.
+    /// Keys go after a colon. Ares prints the recipe for everything it decodes
+    #[arg(long, conflicts_with = "batch")]
+    recipe: Option<Recipe>,
+    /// Print every step of the decoding with its input, output, key and decoder,
+    /// and which checker accepted the plaintext. Useful for writeups and reports
+    #[arg(long, conflicts_with = "batch", overrides_with = "no_explain")]
+    explain: bool,
+    /// Don't print the steps, when a config file or profile set `explain`
+    #[arg(long, overrides_with = "explain")]
+    no_explain: bool,
+    /// Use a profile from the config files, like `ctf`, `forensics` or `fast`.
+    /// Flags still win over the profile
+    #[arg(long)]
+    profile: Option<String>,
+    /// Read this config file after `~/.config/ares/config.toml` and the project's `.ares.toml`
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Print the settings Ares would use, from the config files, profile and flags, then exit
+    #[arg(long)]
+    print_config: bool,
+    /// Explore the search tree in a full-screen terminal UI.
+    /// Expand and prune nodes by hand, inspect each text and pick the answer
+    #[cfg(feature = "tui")]
+    #[arg(long, conflicts_with_all = ["batch", "recipe"])]
+    tui: bool,
+    /// Other things Ares can do, instead of decoding
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// The subcommands, `ares <command>`
+#[derive(Subcommand)]
+enum Command {
+    /// Encode text with a chain of encoders, to build inputs for testing Ares.
+    /// For example `ares encode --chain rot13,base64,hex "hello"`
+    Encode {
+        /// The encoders to run in order, separated by commas. Keys go after a colon, like `caesar:3`.
+        /// Each decoder has an encoder, named like `base64`, `base58_bitcoin` or `hex`
+        #[arg(long)]
+        chain: EncoderChain,
+        /// The text to encode, read from stdin if it's not given
+        text: Option<String>,
+    },
+    /// List every decoder or checker with its tags and popularity, like `ares list decoders`
+    List {
+        /// Whether to list the decoders or the checkers
+        what: Listing,
+        /// Print a JSON array instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Describe a decoder or checker, like `ares explain caesar`
+    Explain {
+        /// The decoder or checker, by its name or the name used in `--recipe` and `--checkers`
+        name: String,
+    },
+}
+
+/// What `ares list` can list
+#[derive(Clone, Copy, ValueEnum)]
+enum Listing {
+    /// Every decoder
+    Decoders,
+    /// Every checker
+    Checkers,
+}
+
+/// What the CLI was asked to do
+pub enum CliInput {
+    /// One text to decode
+    Single(String),
+    /// Many texts to decode in batch mode, one result each
+    Batch(Vec<BatchInput>),
+    /// One text to decode with a known recipe
+    Recipe {
+        /// The decoders to run
+        recipe: Recipe,
+        /// The text to decode
+        text: String,
+    },
+    /// Text to encode with `ares encode`
+    Encode {
+        /// The encoders to run
+        chain: EncoderChain,
+        /// The text to encode
+        text: String,
+    },
+    /// List the decoders or checkers with `ares list`
+    List {
+        /// Whether to list decoders or checkers
+        kind: ComponentKind,
+        /// Print JSON instead of a table
+        json: bool,
+    },
+    /// Describe a decoder or checker with `ares explain`
+    Explain(String),
+    /// Print the settings with `--print-config`
+    PrintConfig {
+        /// The config files the settings were read from
+        files: Vec<PathBuf>,
+        /// The settings after the files, profile and flags
+        settings: Box<Settings>,
+    },
+}
+
+/// One of the texts to decode in batch mode
+#[derive(Debug, PartialEq, Eq)]
+pub struct BatchInput {
+    /// Where the text came from, like `stdin:3` (line 3) or the path of a file
+    pub source: String,
+    /// The text to decode
+    pub text: String,
 }
 
 /// Parse CLI Arguments turns a Clap Opts struct, seen above
 /// Into a library Struct for use within the program
 /// The library struct can be found in the [config](../config) folder.
-/// # Panics
-/// This function can panic when it gets both a file and text input at the same time.
-pub fn parse_cli_args() -> (String, Config) {
+/// If no text or file is given the input is read from stdin.
+/// # Errors
+/// This errors when it gets both a file and text input at the same time,
+/// when there is no input at all, or when the input, config files or settings can't be used.
+pub fn parse_cli_args() -> Result<(CliInput, Config), Error> {
     let mut opts: Opts = Opts::parse();
-    let min_log_level = match opts.verbose {
+    let (config_files, settings) = read_settings(&mut opts)?;
+    let min_log_level = match settings.verbose.unwrap_or_default() {
         0 => "Warn",
         1 => "Info",
         2 => "Debug",
@@ -61,44 +251,162 @@ pub fn parse_cli_args() -> (String, Config) {
         env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, min_log_level),
     );
 
-    // If both the file and text are proivded, panic because we're not sure which one to use
+    let command = match opts.command.take() {
+        Some(Command::Encode { chain, text }) => {
+            let text = match text {
+                Some(text) => text,
+                None => read_stdin()?,
+            };
+            Some(CliInput::Encode { chain, text })
+        }
+        Some(Command::List { what, json }) => Some(CliInput::List {
+            kind: match what {
+                Listing::Decoders => ComponentKind::Decoder,
+                Listing::Checkers => ComponentKind::Checker,
+            },
+            json,
+        }),
+        Some(Command::Explain { name }) => Some(CliInput::Explain(name)),
+        None => None,
+    };
+    if let Some(command) = command {
+        return Ok((command, cli_args_into_config_struct(opts, settings)?));
+    }
+    if opts.print_config {
+        return Ok((
+            CliInput::PrintConfig {
+                files: config_files,
+                settings: Box::new(settings.clone()),
+            },
+            cli_args_into_config_struct(opts, settings)?,
+        ));
+    }
+
+    // If both the file and text are proivded, stop because we're not sure which one to use
     if opts.file.is_some() && opts.text.is_some() {
-        panic_failure_both_input_and_fail_provided();
+        return Err(Error::BothFileAndText);
     }
 
-    let input_text: String = if opts.file.is_some() {
-        read_and_parse_file(opts.file.unwrap())
-    } else {
-        opts.text
-            .expect("Error. No input was provided. Please use ares --help")
+    // Taking them fixes bug where opts.text and opts.file are partially borrowed
+    let input = match (opts.file.take(), opts.text.take()) {
+        (Some(file_path), _) if opts.batch => CliInput::Batch(read_batch_path(&file_path)?),
+        (Some(file_path), _) => CliInput::Single(read_and_parse_file(file_path)?),
+        (None, Some(text)) if opts.batch => CliInput::Batch(split_into_batch("text", &text)),
+        (None, Some(text)) => CliInput::Single(text),
+        (None, None) => {
+            let text = read_stdin()?;
+            if opts.batch {
+                CliInput::Batch(split_into_batch("stdin", &text))
+            } else {
+                CliInput::Single(text)
+            }
+        }
     };
 
-    // Fixes bug where opts.text and opts.file are partially borrowed
-    opts.text = None;
-    opts.file = None;
-
     trace!("Program was called with CLI 😉");
     trace!("Parsed the arguments");
-    trace!("The inputted text is {}", &input_text);
+    if let CliInput::Single(text) = &input {
+        trace!("The inputted text is {}", text);
+    }
+
+    let input = match (input, opts.recipe.take()) {
+        (CliInput::Single(text), Some(recipe)) => CliInput::Recipe { recipe, text },
+        (input, _) => input,
+    };
+
+    let batch = opts.batch;
+    let mut config = cli_args_into_config_struct(opts, settings)?;
+    if matches!(input, CliInput::Recipe { .. }) {
+        // We already know the plaintext is at the end of the recipe
+        config.human_checker_on = false;
+    }
+    if batch {
+        // Each input prints a JSON record instead, and nobody can answer
+        // the human checker for hundreds of inputs at once
+        config.api_mode = true;
+        config.human_checker_on = false;
+    }
+    Ok((input, config))
+}
+
+/// Reads all of stdin when nothing else was given, say `echo aGVsbG8= | ares`.
+/// Like files, the trailing new line is removed.
+fn read_stdin() -> Result<String, Error> {
+    let mut stdin = stdin();
+    // Nothing is piped in, so we'd wait forever for someone to type
+    if stdin.is_terminal() {
+        return Err(Error::NoInput);
+    }
+    let mut contents = String::new();
+    stdin
+        .read_to_string(&mut contents)
+        .map_err(Error::CouldNotReadStdin)?;
+    Ok(strip_trailing_new_line(contents))
+}
+
+/// Batch mode with `--file` decodes each line of a file,
+/// or each file in a directory (not its subdirectories)
+fn read_batch_path(path: &str) -> Result<Vec<BatchInput>, Error> {
+    if !Path::new(path).is_dir() {
+        return Ok(split_into_batch(
+            path,
+            &read_and_parse_file(path.to_string())?,
+        ));
+    }
+    let mut files: Vec<_> = std::fs::read_dir(path)
+        .map_err(|error| Error::CouldNotReadFile {
+            path: path.to_string(),
+            error,
+        })?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    files
+        .into_iter()
+        .map(|file| {
+            let source = file.display().to_string();
+            Ok(BatchInput {
+                text: read_and_parse_file(source.clone())?,
+                source,
+            })
+        })
+        .collect()
+}
 
-    cli_args_into_config_struct(opts, input_text)
+/// Turns every non-empty line into its own input, labelled with its line number
+fn split_into_batch(source: &str, text: &str) -> Vec<BatchInput> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| BatchInput {
+            source: format!("{source}:{}", index + 1),
+            text: line.to_string(),
+        })
+        .collect()
 }
 
 /// When the CLI is called with `-f` to open a file
 /// this function opens it
-/// # Panics
-/// This can panic when opening a file which does not exist!
-pub fn read_and_parse_file(file_path: String) -> String {
-    // TODO pretty match on the errors to provide better output
-    // Else it'll panic
-    let mut file = File::open(file_path).unwrap();
+/// # Errors
+/// This errors when the file doesn't exist or can't be read as text.
+pub fn read_and_parse_file(file_path: String) -> Result<String, Error> {
     let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+    File::open(&file_path)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|error| Error::CouldNotReadFile {
+            path: file_path,
+            error,
+        })?;
     // We can just put the file into the `Opts.text` and the program will work as normal
-    // On Unix systems a line is defined as "\n{text}\n"
-    // https://stackoverflow.com/a/729795
-    // Which means if a user creates a file on Unix, it'll have a new line appended.
-    // This is probably not what they wanted to decode (it is not what I wanted) so we are removing them
+    Ok(strip_trailing_new_line(contents))
+}
+
+/// On Unix systems a line is defined as "\n{text}\n"
+/// https://stackoverflow.com/a/729795
+/// Which means if a user creates a file on Unix, it'll have a new line appended.
+/// This is probably not what they wanted to decode (it is not what I wanted) so we are removing them
+fn strip_trailing_new_line(contents: String) -> String {
     if contents.ends_with(['\n', '\r']) {
         contents.strip_suffix(['\n', '\r']).unwrap().to_owned()
     } else {
@@ -106,23 +414,204 @@ pub fn read_and_parse_file(file_path: String) -> String {
     }
 }
 
-/// Turns our CLI arguments into a config stuct
-fn cli_args_into_config_struct(opts: Opts, text: String) -> (String, Config) {
-    (
-        text,
-        Config {
-            verbose: opts.verbose,
-            lemmeknow_config: Identifier::default(),
-            // default is false, we want default to be true
-            human_checker_on: !opts.disable_human_checker,
-            // These if statements act as defaults
-            timeout: if opts.cracking_timeout.is_none() {
-                30
-            } else {
-                opts.cracking_timeout.unwrap()
-            },
-            api_mode: opts.api_mode.is_some(),
-            regex: opts.regex,
-        },
-    )
+/// When the CLI is called with `-o` this writes the plaintext to the file.
+/// Binary files are written as the exact bytes they were decoded to.
+/// Returns how many bytes were written.
+/// # Errors
+/// This errors if the file can't be created or written to.
+pub fn write_decoded_output(file_path: &str, result: &DecoderResult) -> std::io::Result<usize> {
+    let bytes = result.plaintext_bytes();
+    std::fs::write(file_path, &bytes)?;
+    Ok(bytes.len())
+}
+
+/// Reads the config files and profile, then puts the flags that were given on top.
+/// The flags are taken out of `opts`, the settings have them now.
+/// Returns the config files that were read and the settings.
+/// # Errors
+/// This errors when a config file can't be read, or the profile doesn't exist.
+fn read_settings(opts: &mut Opts) -> Result<(Vec<PathBuf>, Settings), Error> {
+    let (paths, files) = read_config_files(opts.config.as_deref()).map_err(Error::BadConfigFile)?;
+    let file_settings =
+        resolve_settings(&files, opts.profile.as_deref()).map_err(Error::BadConfigFile)?;
+    // Flags which weren't given are None (or empty), so they don't replace the files
+    let non_empty = |values: Vec<String>| (!values.is_empty()).then_some(values);
+    let flags = Settings {
+        verbose: (opts.verbose > 0).then_some(opts.verbose),
+        timeout: opts.cracking_timeout.take(),
+        human_checker: flag_pair(opts.enable_human_checker, opts.disable_human_checker),
+        regex: opts.regex.take(),
+        confidence_threshold: opts.confidence_threshold.take(),
+        languages: opts.languages.take(),
+        wordlists: non_empty(std::mem::take(&mut opts.wordlists)),
+        flag_prefixes: non_empty(std::mem::take(&mut opts.flag_prefixes)),
+        min_rarity: opts.min_rarity.take(),
+        max_rarity: opts.max_rarity.take(),
+        lemmeknow_tags: non_empty(std::mem::take(&mut opts.lemmeknow_tags)),
+        lemmeknow_exclude_tags: non_empty(std::mem::take(&mut opts.lemmeknow_exclude_tags)),
+        boundaryless: flag_pair(opts.boundaryless, opts.no_boundaryless),
+        checkers: opts.checkers.take(),
+        cribs: (!opts.cribs.is_empty()).then(|| std::mem::take(&mut opts.cribs)),
+        explain: flag_pair(opts.explain, opts.no_explain),
+    };
+    Ok((
+        paths,
+        Settings::defaults().merge(file_settings).merge(flags),
+    ))
+}
+
+/// A flag and the flag which turns it off, as a setting.
+/// Clap keeps only the last of the two, and if neither was given the files decide.
+fn flag_pair(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// Turns our CLI arguments and settings into a config stuct.
+/// The settings start from `Settings::defaults`, so every one of them is set.
+/// # Errors
+/// This errors when a wordlist can't be read or the regex isn't valid.
+fn cli_args_into_config_struct(opts: Opts, settings: Settings) -> Result<Config, Error> {
+    let config = Config {
+        verbose: settings.verbose.unwrap_or_default(),
+        lemmeknow_config: Identifier::default()
+            .min_rarity(
+                settings
+                    .min_rarity
+                    .unwrap_or(LEMMEKNOW_DEFAULT_CONFIG.min_rarity)
+                    .clamp(0.0, 1.0),
+            )
+            .max_rarity(
+                settings
+                    .max_rarity
+                    .unwrap_or(LEMMEKNOW_DEFAULT_CONFIG.max_rarity)
+                    .clamp(0.0, 1.0),
+            )
+            .include_tags(&settings.lemmeknow_tags.unwrap_or_default())
+            .exclude_tags(&settings.lemmeknow_exclude_tags.unwrap_or_default())
+            .boundaryless(settings.boundaryless.unwrap_or_default()),
+        human_checker_on: settings.human_checker.unwrap_or(true),
+        timeout: settings.timeout.unwrap_or_default(),
+        api_mode: opts.api_mode.is_some(),
+        regex: settings.regex,
+        confidence_threshold: settings
+            .confidence_threshold
+            .unwrap_or_default()
+            .clamp(0.0, 1.0),
+        languages: settings.languages.unwrap_or_default(),
+        wordlists: load_wordlists(&settings.wordlists.unwrap_or_default())
+            .map_err(Error::CouldNotReadWordlist)?,
+        flag_prefixes: settings.flag_prefixes.unwrap_or_default(),
+        output_file: opts.output,
+        checkers: settings.checkers,
+        cribs: settings.cribs.unwrap_or_default(),
+        #[cfg(feature = "tui")]
+        tui: opts.tui,
+        #[cfg(not(feature = "tui"))]
+        tui: false,
+        explain: settings.explain.unwrap_or_default(),
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        read_and_parse_file, read_batch_path, read_settings, split_into_batch,
+        write_decoded_output, Opts,
+    };
+    use crate::config::Config;
+    use crate::decoders::hexadecimal_decoder::encode_hexadecimal;
+    use crate::error::Error;
+    use crate::perform_cracking;
+    use clap::Parser;
+
+    #[test]
+    fn test_split_into_batch_skips_empty_lines() {
+        let inputs = split_into_batch("stdin", "b2xsZWg=\n\n  \r\naGk=\r\n");
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].source, "stdin:1");
+        assert_eq!(inputs[0].text, "b2xsZWg=");
+        assert_eq!(inputs[1].source, "stdin:4");
+        assert_eq!(inputs[1].text, "aGk=");
+    }
+
+    #[test]
+    fn test_read_batch_path_reads_each_file_in_a_directory() {
+        let inputs = read_batch_path("tests/test_fixtures").unwrap();
+        assert!(inputs.len() > 1);
+        assert!(inputs
+            .windows(2)
+            .all(|pair| pair[0].source < pair[1].source));
+        assert!(inputs
+            .iter()
+            .any(|input| input.source.ends_with("base64_3_times_with_no_new_line")));
+    }
+
+    #[test]
+    fn test_missing_file_is_an_error() {
+        let error =
+            read_and_parse_file("tests/test_fixtures/does_not_exist".to_string()).unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::CouldNotReadFile { path, .. } if path == "tests/test_fixtures/does_not_exist"
+        ));
+        assert!(read_batch_path("tests/test_fixtures/does_not_exist").is_err());
+    }
+
+    #[test]
+    fn test_output_file_has_the_exact_bytes_of_a_png() {
+        // 0x89 isn't UTF-8 on its own, written as text it would become `c2 89`
+        let png = [
+            0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48,
+            0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00,
+            0x00, 0x1f, 0x15, 0xc4, 0x89,
+        ];
+        let text: String = png.iter().copied().map(char::from).collect();
+        let result = perform_cracking(&encode_hexadecimal(&text), Config::default()).unwrap();
+
+        let path = std::env::temp_dir().join(format!("ares_png_{}.png", std::process::id()));
+        let written = write_decoded_output(path.to_str().unwrap(), &result).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(written, png.len());
+        assert_eq!(bytes, png);
+    }
+
+    #[test]
+    fn test_flags_turn_off_what_a_config_file_turned_on() {
+        let path = std::env::temp_dir().join(format!("ares_flags_{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "human_checker = true\nboundaryless = true\nexplain = true\n",
+        )
+        .unwrap();
+        let config = path.to_str().unwrap();
+
+        let mut opts = Opts::try_parse_from(["ares", "--config", config]).unwrap();
+        let (_, settings) = read_settings(&mut opts).unwrap();
+        assert_eq!(settings.human_checker, Some(true));
+        assert_eq!(settings.boundaryless, Some(true));
+        assert_eq!(settings.explain, Some(true));
+
+        let mut opts = Opts::try_parse_from([
+            "ares",
+            "--config",
+            config,
+            "-d",
+            "--no-boundaryless",
+            "--explain",
+            "--no-explain",
+        ])
+        .unwrap();
+        let (_, settings) = read_settings(&mut opts).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(settings.human_checker, Some(false));
+        assert_eq!(settings.boundaryless, Some(false));
+        assert_eq!(settings.explain, Some(false));
+    }
 }
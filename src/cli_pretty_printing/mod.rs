@@ -2,6 +2,11 @@
 /// and make sure each one is up to our standards. Previously a rogue print statement that went off at an edge case
 /// would look a bit ugly and not the same UI as others.
 /// We can also do things like check for logic or share information / functions which would be a bit messy in the main code.
+use crate::checkers::checker_result::CheckResult;
+use crate::config::file::Settings;
+use crate::error::Error;
+use crate::recipe::Recipe;
+use crate::registry::ComponentInfo;
 use crate::DecoderResult;
 
 /// The output function is used to print the output of the program.
@@ -11,7 +16,8 @@ pub fn program_exiting_successful_decoding(result: DecoderResult) {
     if config.api_mode {
         return;
     }
-    let plaintext = result.text;
+    let plaintext = &result.text;
+    let recipe = Recipe::from_path(&result.path);
     // calculate path
     let decoded_path = result
         .path
@@ -32,6 +38,114 @@ pub fn program_exiting_successful_decoding(result: DecoderResult) {
         ansi_term::Colour::Yellow.bold().paint(&plaintext[0]),
         decoded_path_string
     );
+    if let (Some(matched), Some(last)) = (result.matched(), result.path.last()) {
+        println!(
+            "The {} found: {}",
+            last.checker_name,
+            ansi_term::Colour::Yellow.bold().paint(matched)
+        );
+    }
+    if !recipe.steps.is_empty() {
+        println!(
+            "Decode more like this with --recipe {}",
+            ansi_term::Colour::Yellow.bold().paint(recipe.to_string())
+        );
+    }
+}
+
+/// How much of each step's input and output `--explain` shows
+const EXPLAIN_PREVIEW_CHARS: usize = 100;
+
+/// Prints each step of the decoding for `--explain`, so the result can be checked by hand.
+/// `from_recipe` is true when the steps came from `--recipe` rather than a search,
+/// as a recipe's last step doesn't have to be accepted by a checker.
+/// Only prints if explain is on, and not in API mode.
+pub fn decoding_explained(result: &DecoderResult, from_recipe: bool) {
+    let config = crate::config::get_config();
+    if !config.explain || config.api_mode {
+        return;
+    }
+    println!("{}", explanation(result, from_recipe));
+}
+
+/// The steps `--explain` prints, each with its key, input and output
+fn explanation(result: &DecoderResult, from_recipe: bool) -> String {
+    let mut lines = Vec::new();
+    let default_decoder = crate::decoders::interface::Decoder::default().name;
+    let steps: Vec<_> = result
+        .path
+        .iter()
+        .filter(|step| step.decoder != default_decoder)
+        .collect();
+    for (index, step) in steps.iter().enumerate() {
+        // Each step's output is the next step's input, and the last step's is the plaintext
+        let output = steps.get(index + 1).map_or_else(
+            || result.text[0].as_str(),
+            |next| next.encrypted_text.as_str(),
+        );
+        lines.push(format!(
+            "Step {}: {}",
+            index + 1,
+            ansi_term::Colour::Yellow.bold().paint(step.decoder)
+        ));
+        lines.push(format!("  {}", step.description));
+        if !step.link.is_empty() {
+            lines.push(format!("  Read more: {}", step.link));
+        }
+        if let Some(key) = &step.key {
+            lines.push(format!(
+                "  Key: {}",
+                ansi_term::Colour::Yellow.bold().paint(key)
+            ));
+        }
+        lines.push(format!(
+            "  Input:  '{}'",
+            truncate(&step.encrypted_text, EXPLAIN_PREVIEW_CHARS)
+        ));
+        lines.push(format!(
+            "  Output: '{}'",
+            truncate(output, EXPLAIN_PREVIEW_CHARS)
+        ));
+    }
+    match result.path.last() {
+        Some(last) if last.success => {
+            let accepted = if steps.is_empty() {
+                "The input was already the plaintext, so nothing was decoded. It was accepted by the"
+            } else {
+                "The plaintext was accepted by the"
+            };
+            lines.push(format!(
+                "{accepted} {} ({:.0}% confident)",
+                ansi_term::Colour::Yellow.bold().paint(last.checker_name),
+                last.confidence * 100.0
+            ));
+            if let Some(matched) = &last.matched {
+                lines.push(format!(
+                    "  It found: '{}'",
+                    truncate(matched, EXPLAIN_PREVIEW_CHARS)
+                ));
+            }
+        }
+        _ if from_recipe => {
+            lines.push("No checker accepted the plaintext, it's the end of the recipe.".to_string())
+        }
+        _ => lines.push("No checker accepted the plaintext.".to_string()),
+    }
+    lines.join("\n") + "\n"
+}
+
+/// When the user asked for the plaintext to go to a file, tell them where it went.
+/// If API mode is on, it will not print.
+pub fn program_exiting_wrote_output_file(file_path: &str, bytes_written: usize) {
+    let config = crate::config::get_config();
+    if config.api_mode {
+        return;
+    }
+    println!(
+        "The plaintext has been written to {} ({} bytes)",
+        ansi_term::Colour::Yellow.bold().paint(file_path),
+        bytes_written
+    );
 }
 
 /// The output function is used to print the output of the program.
@@ -43,8 +157,7 @@ pub fn decoded_how_many_times(depth: u32) {
 
     // Gets how many decoders we have
     // Then we add 25 for Caesar
-    let decoders = crate::filtration_system::filter_and_get_decoders(&DecoderResult::default());
-    let decoded_times_int = depth * (decoders.components.len() as u32 + 25);
+    let decoded_times_int = depth * (crate::registry::decoders().len() as u32 + 25);
 
     let time_took = calculate_time_took(decoded_times_int);
 
@@ -55,24 +168,90 @@ pub fn decoded_how_many_times(depth: u32) {
 /// Whenever the human checker checks for text, this function is run.
 /// The human checker checks to see if API mdoe is runnign inside of it
 /// rather than doing it here at the printing level
-pub fn human_checker_check(description: &str, text: &str) {
+pub fn human_checker_check(path: &str, check_result: &CheckResult) {
+    let reason = if check_result.description.is_empty() {
+        check_result.checker_name.to_string()
+    } else {
+        format!(
+            "{} ({})",
+            check_result.description, check_result.checker_name
+        )
+    };
     println!(
-        "🕵️ I think the plaintext is {}.\nPossible plaintext: '{}' (y/N): ",
-        ansi_term::Colour::Yellow.bold().paint(description),
-        ansi_term::Colour::Yellow.bold().paint(text)
+        "🕵️ I think the plaintext is {}.\nDecoded with: {}\nPossible plaintext: '{}'\n[y]es / [N]o / reject everything from this [c]hecker / show [m]ore / [q]uit: ",
+        ansi_term::Colour::Yellow.bold().paint(reason),
+        ansi_term::Colour::Yellow.bold().paint(path),
+        ansi_term::Colour::Yellow
+            .bold()
+            .paint(truncate(&check_result.text, HUMAN_CHECKER_PREVIEW_CHARS))
     )
 }
 
-/// When Ares has failed to decode something, print this message
-pub fn failed_to_decode() {
-    let config = crate::config::get_config();
-    if config.api_mode {
-        return;
+/// How much of the possible plaintext the human checker shows before they ask for more
+const HUMAN_CHECKER_PREVIEW_CHARS: usize = 200;
+
+/// Cuts long text short, saying how much was left out
+fn truncate(text: &str, max_chars: usize) -> String {
+    let length = text.chars().count();
+    if length <= max_chars {
+        return text.to_string();
     }
+    let start: String = text.chars().take(max_chars).collect();
+    format!("{start}… ({} more characters)", length - max_chars)
+}
+
+/// When the human asks for more, show the whole text and all we know about the checker
+pub fn human_checker_show_more(path: &str, check_result: &CheckResult) {
+    println!(
+        "Decoded with: {}\nChecker: {} - {}\nReason: {}\nConfidence: {:.2}\nMore info: {}\nFull text:\n{}\n[y]es / [N]o / reject everything from this [c]hecker / [q]uit: ",
+        path,
+        check_result.checker_name,
+        check_result.checker_description,
+        check_result.description,
+        check_result.confidence,
+        check_result.link,
+        ansi_term::Colour::Yellow.bold().paint(&check_result.text)
+    )
+}
 
-    println!("⛔️ Ares has failed to decode the text.");
-    println!("If you want more help, please ask in #coded-messages in our Discord http://discord.skerritt.blog");
+/// The human checker didn't understand the reply
+pub fn human_checker_unknown_reply() {
+    println!("Please answer y, n, c, m or q: ");
+}
+
+/// The human asked us to stop
+pub fn human_checker_quitting() {
+    println!("Stopping the search as you asked.");
+}
+
+/// Ares is stopping because of an error, so tell the user what went wrong.
+/// Not finding the plaintext is a normal outcome and follows API mode like the other results,
+/// while errors about the input are always printed (to stderr) as nothing else will be.
+pub fn program_exiting_with_error(error: &Error) {
+    match error {
+        Error::NotFound | Error::Timeout { .. } => {
+            if crate::config::get_config().api_mode {
+                return;
+            }
+            println!("⛔️ {error}");
+            println!("If you want more help, please ask in #coded-messages in our Discord http://discord.skerritt.blog");
+        }
+        Error::RecipeFailed(failure) => {
+            if crate::config::get_config().api_mode {
+                return;
+            }
+            println!(
+                "⛔️ The recipe failed at {}.\nIt was given: '{}'",
+                ansi_term::Colour::Yellow.bold().paint(failure.to_string()),
+                ansi_term::Colour::Yellow
+                    .bold()
+                    .paint(truncate(&failure.text, HUMAN_CHECKER_PREVIEW_CHARS))
+            );
+        }
+        _ => eprintln!("⛔️ {error}"),
+    }
 }
+
 /// Calculate how long it would take to decode this in Ciphey
 fn calculate_time_took(decoded_times_int: u32) -> String {
     // TODO if we grab how long the programs been running for (see timer) we can make some nice stats like:
@@ -125,24 +304,150 @@ pub fn return_early_because_input_text_is_plaintext() {
     println!("Your input text is the plaintext 🥳");
 }
 
-/// The user has provided both textual input and file input
-/// # Panics
-/// This function panics and is only used in the CLI.
-pub fn panic_failure_both_input_and_fail_provided() {
-    let config = crate::config::get_config();
-    if config.api_mode {
-        return;
+/// Prints the settings for `--print-config` as a config file, so it can be saved as one
+pub fn effective_config(files: &[std::path::PathBuf], settings: &Settings) {
+    if files.is_empty() {
+        println!("# No config files were found, these are the defaults and flags");
+    }
+    for file in files {
+        println!("# Read from {}", file.display());
     }
-    panic!("Failed -- both file and text were provided. Please only use one.")
+    print!("{settings}");
 }
 
-/// The user has not provided any input.
-/// # Panics
-/// This function panics and is only used in the CLI.
-pub fn panic_failure_no_input_provided() {
-    let config = crate::config::get_config();
-    if config.api_mode {
+/// Prints the output of `ares encode`, on its own so it can be piped into other tools
+pub fn encoded_text(encoded: &str) {
+    println!("{encoded}");
+}
+
+/// Prints the result of one input in batch mode as a line of JSON.
+/// Unlike the other outputs this prints in API mode, because batch mode turns API mode on
+/// to stop each search printing its own output.
+pub fn batch_result(source: &str, input: &str, result: Result<&DecoderResult, &Error>) {
+    let record = match result {
+        Ok(result) => serde_json::json!({
+            "source": source,
+            "input": input,
+            "success": true,
+            "plaintext": result.text.first(),
+            "decoders": result.path.iter().map(|c| c.decoder).collect::<Vec<_>>(),
+            "checker": result.path.last().map(|c| c.checker_name),
+            "matched": result.matched(),
+            "error": null,
+        }),
+        Err(error) => serde_json::json!({
+            "source": source,
+            "input": input,
+            "success": false,
+            "plaintext": null,
+            "decoders": [],
+            "checker": null,
+            "matched": null,
+            "error": error.to_string(),
+        }),
+    };
+    println!("{record}");
+}
+
+/// How much of a description `ares list` shows in its table
+const LIST_DESCRIPTION_CHARS: usize = 60;
+
+/// Prints the decoders or checkers for `ares list`, as a table or a JSON array
+pub fn list_components(components: &[ComponentInfo], json: bool) {
+    if json {
+        let components: Vec<serde_json::Value> =
+            components.iter().map(ComponentInfo::to_json).collect();
+        println!("{}", serde_json::Value::Array(components));
         return;
     }
-    panic!("Failed -- no input was provided. Please use -t for text or -f for files.")
+    let rows: Vec<[String; 5]> = components
+        .iter()
+        .map(|component| {
+            [
+                component.name.to_string(),
+                component.short_name.clone().unwrap_or_default(),
+                format!("{:.2}", component.popularity),
+                component.tags.join(", "),
+                // The table has no room for how much was cut, `ares explain` shows it all
+                match component
+                    .description
+                    .char_indices()
+                    .nth(LIST_DESCRIPTION_CHARS)
+                {
+                    Some((end, _)) => format!("{}…", &component.description[..end]),
+                    None => component.description.to_string(),
+                },
+            ]
+        })
+        .collect();
+    let header = ["Name", "Use as", "Popularity", "Tags", "Description"].map(String::from);
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let format_row = |row: &[String; 5]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    println!(
+        "{}",
+        ansi_term::Style::new().bold().paint(format_row(&header))
+    );
+    for row in &rows {
+        println!("{}", format_row(row));
+    }
+}
+
+/// Prints everything we know about a decoder or checker for `ares explain`
+pub fn explain_component(component: &ComponentInfo) {
+    let name = ansi_term::Colour::Yellow.bold().paint(component.name);
+    match &component.short_name {
+        Some(short_name) => println!(
+            "{name} ({}, use as {})",
+            component.kind,
+            ansi_term::Colour::Yellow.bold().paint(short_name)
+        ),
+        None => println!("{name} ({})", component.kind),
+    }
+    println!("{}", component.description);
+    println!("Tags: {}", component.tags.join(", "));
+    println!("Popularity: {:.2}", component.popularity);
+    if !component.link.is_empty() {
+        println!("Read more: {}", component.link);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::explanation;
+    use crate::config::Config;
+    use crate::encoders::EncoderChain;
+    use crate::perform_cracking;
+
+    #[test]
+    fn test_explanation_has_the_key_of_every_step() {
+        // Caesar is the first step, not the one the checkers accepted
+        let encoded = "base64,caesar:25"
+            .parse::<EncoderChain>()
+            .unwrap()
+            .encode("https://www.google.com")
+            .unwrap();
+        let result = perform_cracking(&encoded, Config::default()).unwrap();
+        let explained = explanation(&result, false);
+        let key = format!("Key: {}", ansi_term::Colour::Yellow.bold().paint("25"));
+        let caesar = explained.find("Caesar Cipher").unwrap();
+        let base64 = explained.find("Step 2").unwrap();
+        assert!(explained[caesar..base64].contains(&key));
+        assert!(explained.contains("The plaintext was accepted by the"));
+    }
 }
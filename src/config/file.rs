@@ -0,0 +1,518 @@
+//! Config files save typing the same flags every run.
+//! Ares reads the per-user file (`~/.config/ares/config.toml`) and then the per-project
+//! file (`.ares.toml` in the current directory or the nearest one above it).
+//! Later files win, and flags on the command line win over every file.
+//!
+//! Files can also define profiles, like `[profiles.ctf]`, which are picked with
+//! `--profile ctf` or `profile = "ctf"`. Ares comes with the `ctf`, `forensics` and `fast`
+//! profiles, and files can change those or add their own.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use toml::Table;
+
+use super::{default_languages, LEMMEKNOW_DEFAULT_CONFIG};
+use crate::checkers::{checker_expression::CheckerExpression, crib_checker::Crib};
+use crate::storage::SUPPORTED_LANGUAGES;
+
+/// The profiles every install has, files can change them like any other profile
+const BUILT_IN_PROFILES: &str = r#"
+# Searches longer and stops at anything that looks like a flag
+[profiles.ctf]
+timeout = 60
+checkers = "flag OR crib OR lemmeknow OR english OR quadgram"
+
+# Looks for files and structured data rather than English
+[profiles.forensics]
+timeout = 120
+checkers = "file_type OR structured_data OR lemmeknow"
+
+# A short search for English, without asking anything
+[profiles.fast]
+timeout = 5
+human_checker = false
+languages = ["en"]
+"#;
+
+/// The name of the per-project config file
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".ares.toml";
+
+/// Settings from a config file, a profile or the command line.
+/// Anything left as None wasn't set, so it comes from somewhere else.
+/// The keys in files are the same as the long command line flags, with underscores.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// How much we log, like `-vv`
+    pub verbose: Option<u8>,
+    /// How many seconds to search for
+    pub timeout: Option<u32>,
+    /// Whether to ask the human if the plaintext is right
+    pub human_checker: Option<bool>,
+    /// The regex the plaintext has to match
+    pub regex: Option<String>,
+    /// How confident a checker must be before we accept the plaintext
+    #[serde(default, deserialize_with = "fraction")]
+    pub confidence_threshold: Option<f64>,
+    /// The languages to look for plaintext in
+    #[serde(default, deserialize_with = "languages")]
+    pub languages: Option<Vec<String>>,
+    /// Files or directories of words to count as plaintext
+    pub wordlists: Option<Vec<String>>,
+    /// CTF flag prefixes to look for
+    pub flag_prefixes: Option<Vec<String>>,
+    /// Only accept LemmeKnow matches at least this rare
+    #[serde(default, deserialize_with = "rarity", serialize_with = "write_rarity")]
+    pub min_rarity: Option<f32>,
+    /// Only accept LemmeKnow matches at most this rare
+    #[serde(default, deserialize_with = "rarity", serialize_with = "write_rarity")]
+    pub max_rarity: Option<f32>,
+    /// Only accept LemmeKnow matches with one of these tags
+    pub lemmeknow_tags: Option<Vec<String>>,
+    /// Ignore LemmeKnow matches with any of these tags
+    pub lemmeknow_exclude_tags: Option<Vec<String>>,
+    /// Let LemmeKnow find patterns anywhere in the text
+    pub boundaryless: Option<bool>,
+    /// Which checkers decide what is plaintext
+    #[serde(
+        default,
+        deserialize_with = "checker_expression",
+        serialize_with = "write_checker_expression"
+    )]
+    pub checkers: Option<CheckerExpression>,
+    /// Known pieces of the plaintext
+    #[serde(default, deserialize_with = "cribs", serialize_with = "write_cribs")]
+    pub cribs: Option<Vec<Crib>>,
+    /// Print every step of the decoding
+    pub explain: Option<bool>,
+}
+
+impl Settings {
+    /// What Ares uses when nothing else sets it
+    pub fn defaults() -> Settings {
+        Settings {
+            verbose: Some(0),
+            timeout: Some(30),
+            human_checker: Some(true),
+            regex: None,
+            confidence_threshold: Some(0.0),
+            languages: Some(default_languages()),
+            wordlists: Some(vec![]),
+            flag_prefixes: Some(vec![]),
+            min_rarity: Some(LEMMEKNOW_DEFAULT_CONFIG.min_rarity),
+            max_rarity: Some(LEMMEKNOW_DEFAULT_CONFIG.max_rarity),
+            lemmeknow_tags: Some(vec![]),
+            lemmeknow_exclude_tags: Some(vec![]),
+            boundaryless: Some(false),
+            checkers: None,
+            cribs: Some(vec![]),
+            explain: Some(false),
+        }
+    }
+
+    /// These settings, with anything set in `other` replacing them
+    pub fn merge(self, other: Settings) -> Settings {
+        Settings {
+            verbose: other.verbose.or(self.verbose),
+            timeout: other.timeout.or(self.timeout),
+            human_checker: other.human_checker.or(self.human_checker),
+            regex: other.regex.or(self.regex),
+            confidence_threshold: other.confidence_threshold.or(self.confidence_threshold),
+            languages: other.languages.or(self.languages),
+            wordlists: other.wordlists.or(self.wordlists),
+            flag_prefixes: other.flag_prefixes.or(self.flag_prefixes),
+            min_rarity: other.min_rarity.or(self.min_rarity),
+            max_rarity: other.max_rarity.or(self.max_rarity),
+            lemmeknow_tags: other.lemmeknow_tags.or(self.lemmeknow_tags),
+            lemmeknow_exclude_tags: other.lemmeknow_exclude_tags.or(self.lemmeknow_exclude_tags),
+            boundaryless: other.boundaryless.or(self.boundaryless),
+            checkers: other.checkers.or(self.checkers),
+            cribs: other.cribs.or(self.cribs),
+            explain: other.explain.or(self.explain),
+        }
+    }
+}
+
+impl fmt::Display for Settings {
+    /// Writes the settings as a config file, leaving out anything that isn't set
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&toml::to_string(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// Reads a number from 0 to 1
+fn fraction<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    let number = f64::deserialize(deserializer)?;
+    if !(0.0..=1.0).contains(&number) {
+        return Err(de::Error::custom(format!(
+            "should be from 0.0 to 1.0, not {number}"
+        )));
+    }
+    Ok(Some(number))
+}
+
+/// Reads a LemmeKnow rarity, which is a number from 0 to 1
+fn rarity<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f32>, D::Error> {
+    Ok(fraction(deserializer)?.map(|number| number as f32))
+}
+
+/// Writes a rarity the way it was set (0.1 rather than 0.10000000149011612)
+fn write_rarity<S: Serializer>(rarity: &Option<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+    rarity
+        .map(|number| number.to_string().parse().unwrap_or(f64::from(number)))
+        .serialize(serializer)
+}
+
+/// Reads the languages, which have to be ones we have words for
+fn languages<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<String>>, D::Error> {
+    let languages = Vec::<String>::deserialize(deserializer)?;
+    if let Some(unknown) = languages.iter().find(|language| {
+        *language != "en"
+            && !SUPPORTED_LANGUAGES
+                .iter()
+                .any(|(code, _)| code == *language)
+    }) {
+        return Err(de::Error::custom(format!(
+            "can't include {unknown:?}, the languages are {}",
+            default_languages().join(", ")
+        )));
+    }
+    Ok(Some(languages))
+}
+
+/// Reads a checker expression, like `"flag OR english"`
+fn checker_expression<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<CheckerExpression>, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map(Some)
+        .map_err(|error| de::Error::custom(format!("isn't a checker expression, {error}")))
+}
+
+/// Writes a checker expression the way it's read
+fn write_checker_expression<S: Serializer>(
+    checkers: &Option<CheckerExpression>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    checkers
+        .as_ref()
+        .map(ToString::to_string)
+        .serialize(serializer)
+}
+
+/// Reads the cribs, like `["start:flag{"]`
+fn cribs<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<Crib>>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|crib| crib.parse())
+        .collect::<Result<_, String>>()
+        .map(Some)
+        .map_err(de::Error::custom)
+}
+
+/// Writes the cribs the way they're read
+fn write_cribs<S: Serializer>(cribs: &Option<Vec<Crib>>, serializer: S) -> Result<S::Ok, S::Error> {
+    cribs
+        .as_ref()
+        .map(|cribs| cribs.iter().map(ToString::to_string).collect::<Vec<_>>())
+        .serialize(serializer)
+}
+
+/// A config file, with its settings and profiles
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigFile {
+    /// The settings at the top of the file
+    pub settings: Settings,
+    /// The profile to use when `--profile` isn't given
+    pub profile: Option<String>,
+    /// The `[profiles.name]` tables, by name
+    pub profiles: BTreeMap<String, Settings>,
+}
+
+impl FromStr for ConfigFile {
+    type Err = String;
+
+    fn from_str(document: &str) -> Result<Self, Self::Err> {
+        let mut table = document
+            .parse::<Table>()
+            .map_err(|error| error.to_string())?;
+        let profile = table
+            .remove("profile")
+            .map(|profile| {
+                String::deserialize(profile)
+                    .map_err(|error| format!("profile: {}", error.message()))
+            })
+            .transpose()?;
+        let profiles = table
+            .remove("profiles")
+            .map(|profiles| {
+                BTreeMap::<String, Table>::deserialize(profiles)
+                    .map_err(|_| "profiles: profiles go in [profiles.name] tables".to_string())
+            })
+            .transpose()?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, profile)| {
+                let settings =
+                    settings(profile).map_err(|error| format!("[profiles.{name}] {error}"))?;
+                Ok((name, settings))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(ConfigFile {
+            settings: settings(table)?,
+            profile,
+            profiles,
+        })
+    }
+}
+
+impl ConfigFile {
+    /// This file with its relative wordlist paths made relative to `directory`,
+    /// the one the file is in, so they don't depend on where Ares is run from
+    fn relative_to(mut self, directory: &Path) -> ConfigFile {
+        for settings in std::iter::once(&mut self.settings).chain(self.profiles.values_mut()) {
+            for wordlist in settings.wordlists.iter_mut().flatten() {
+                *wordlist = directory.join(&*wordlist).to_string_lossy().into_owned();
+            }
+        }
+        self
+    }
+}
+
+/// Reads the settings in a table one key at a time, so errors say which setting is wrong
+fn settings(table: Table) -> Result<Settings, String> {
+    table
+        .into_iter()
+        .try_fold(Settings::default(), |settings, (key, value)| {
+            let setting = Settings::deserialize(Table::from_iter([(key.clone(), value)]))
+                .map_err(|error| format!("{key}: {}", error.message()))?;
+            Ok(settings.merge(setting))
+        })
+}
+
+/// Layers the config files, lowest priority first, on top of the built-in profiles,
+/// and returns the settings with the chosen profile applied.
+/// The profile is the one given, or else the one the files pick.
+/// # Errors
+/// Returns an error if there's no profile with that name.
+pub fn resolve_settings(files: &[ConfigFile], profile: Option<&str>) -> Result<Settings, String> {
+    let built_in: ConfigFile = BUILT_IN_PROFILES.parse()?;
+    let mut settings = Settings::default();
+    let mut profiles = BTreeMap::new();
+    let mut chosen_profile = None;
+    for file in std::iter::once(&built_in).chain(files) {
+        settings = settings.merge(file.settings.clone());
+        for (name, profile) in &file.profiles {
+            let merged = profiles
+                .remove(name)
+                .unwrap_or_else(Settings::default)
+                .merge(profile.clone());
+            profiles.insert(name.clone(), merged);
+        }
+        chosen_profile = file.profile.clone().or(chosen_profile);
+    }
+
+    match profile.map(str::to_string).or(chosen_profile) {
+        Some(name) => match profiles.remove(&name) {
+            Some(profile) => Ok(settings.merge(profile)),
+            None => Err(format!(
+                "there's no profile called {name:?}, the profiles are {}",
+                profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+            )),
+        },
+        None => Ok(settings),
+    }
+}
+
+/// Where the config files can be, lowest priority first.
+/// That's the per-user file, then the nearest per-project file.
+pub fn config_file_paths() -> Vec<PathBuf> {
+    let user_config_directory = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|directory| !directory.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from));
+    let user_file =
+        user_config_directory.map(|directory| directory.join("ares").join("config.toml"));
+    let project_file = std::env::current_dir().ok().and_then(|directory| {
+        directory
+            .ancestors()
+            .map(|directory| directory.join(PROJECT_CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+    });
+    user_file.into_iter().chain(project_file).collect()
+}
+
+/// Reads the config files that exist, lowest priority first, with the file
+/// given by `--config` last. That one has to exist.
+/// Returns the paths read and their contents.
+/// Relative wordlist paths in a file are relative to the file.
+/// # Errors
+/// Returns which file is wrong and why, if one can't be read or parsed.
+pub fn read_config_files(
+    extra_file: Option<&Path>,
+) -> Result<(Vec<PathBuf>, Vec<ConfigFile>), String> {
+    let mut paths: Vec<PathBuf> = config_file_paths()
+        .into_iter()
+        .filter(|path| path.is_file())
+        .collect();
+    paths.extend(extra_file.map(Path::to_path_buf));
+    let files = paths
+        .iter()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|error| error.to_string())
+                .and_then(|contents| contents.parse::<ConfigFile>())
+                .map(|file| file.relative_to(path.parent().unwrap_or(Path::new(""))))
+                .map_err(|error| format!("{}: {error}", path.display()))
+        })
+        .collect::<Result<_, String>>()?;
+    Ok((paths, files))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_config_files, resolve_settings, ConfigFile, Settings};
+
+    fn file(document: &str) -> ConfigFile {
+        document.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_settings_and_profiles() {
+        let config = file(
+            r#"
+            profile = "work"
+            timeout = 10
+            cribs = ["start:flag{"]
+            [profiles.work]
+            human_checker = false
+            checkers = "regex AND english"
+            "#,
+        );
+        assert_eq!(config.profile.as_deref(), Some("work"));
+        assert_eq!(config.settings.timeout, Some(10));
+        assert_eq!(config.settings.cribs.as_ref().unwrap()[0].text, "flag{");
+        let work = &config.profiles["work"];
+        assert_eq!(work.human_checker, Some(false));
+        assert_eq!(
+            work.checkers.as_ref().unwrap().to_string(),
+            "regex AND english"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_say_the_setting() {
+        let error = |document: &str| document.parse::<ConfigFile>().unwrap_err();
+        assert!(error("timeout = 5\ntimout = 6").starts_with("timout: unknown field"));
+        assert_eq!(
+            error("timeout = \"5\""),
+            "timeout: invalid type: string \"5\", expected u32"
+        );
+        assert!(error("timeout = -5").starts_with("timeout: invalid value"));
+        assert!(error("min_rarity = 2.0").contains("0.0 to 1.0"));
+        assert!(error("languages = [\"xx\"]").contains("\"xx\""));
+        assert!(error("[checkers]\nflag = true").starts_with("checkers:"));
+        assert!(error("checkers = \"flag AND\"").starts_with("checkers: isn't"));
+        assert!(error("[profiles.work]\ntimout = 6").starts_with("[profiles.work] timout:"));
+        assert!(error("timeout = ").contains("line 1"));
+    }
+
+    #[test]
+    fn test_parse_any_valid_toml() {
+        let config = file(
+            r#"
+            profiles.quick = { timeout = 3, languages = ["en", "fr"] }
+            regex = '''fl"ag\{'''
+            flag_prefixes = [
+                "HTB",
+                "picoCTF", # the prefix picoCTF uses
+            ]
+            "#,
+        );
+        assert_eq!(config.profiles["quick"].timeout, Some(3));
+        assert_eq!(config.settings.regex.as_deref(), Some("fl\"ag\\{"));
+        assert_eq!(
+            config.settings.flag_prefixes,
+            Some(vec!["HTB".to_string(), "picoCTF".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_later_files_and_profiles_win() {
+        let user = file("timeout = 10\nverbose = 1\n[profiles.ctf]\ntimeout = 90");
+        let project = file("timeout = 20\nprofile = \"fast\"");
+        let files = [user, project];
+
+        let settings = resolve_settings(&files, None).unwrap();
+        // The project picked the built-in fast profile, which sets the timeout
+        assert_eq!(settings.timeout, Some(5));
+        assert_eq!(settings.verbose, Some(1));
+        assert_eq!(settings.human_checker, Some(false));
+
+        // The user's ctf profile changes the built-in one
+        let ctf = resolve_settings(&files, Some("ctf")).unwrap();
+        assert_eq!(ctf.timeout, Some(90));
+        assert!(ctf.checkers.is_some());
+
+        let error = resolve_settings(&files, Some("nope")).unwrap_err();
+        assert!(error.contains("ctf, fast, forensics"));
+    }
+
+    #[test]
+    fn test_built_in_profiles_parse() {
+        for profile in ["ctf", "forensics", "fast"] {
+            assert!(resolve_settings(&[], Some(profile))
+                .unwrap()
+                .timeout
+                .is_some());
+        }
+        assert_eq!(resolve_settings(&[], None).unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn test_display_reads_back() {
+        let settings = Settings::defaults().merge(
+            file("regex = \"fl\\\"ag\"\ncribs = [\"end:}\"]\ncheckers = \"flag OR english\"")
+                .settings,
+        );
+        let written = settings.to_string();
+        assert_eq!(file(&written).settings, settings);
+    }
+
+    #[test]
+    fn test_wordlists_are_relative_to_the_file() {
+        let directory = std::env::temp_dir().join(format!("ares_config_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("ares.toml");
+        let absolute = std::env::temp_dir().join("absolute.txt");
+        std::fs::write(
+            &path,
+            format!(
+                "wordlists = [\"words.txt\", {:?}]\n[profiles.ctf]\nwordlists = [\"lists\"]\n",
+                absolute.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+
+        let (_, files) = read_config_files(Some(&path)).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+        let config = files.last().unwrap();
+        assert_eq!(
+            config.settings.wordlists,
+            Some(vec![
+                directory.join("words.txt").to_string_lossy().into_owned(),
+                absolute.to_string_lossy().into_owned(),
+            ])
+        );
+        assert_eq!(
+            config.profiles["ctf"].wordlists,
+            Some(vec![directory.join("lists").to_string_lossy().into_owned()])
+        );
+    }
+}
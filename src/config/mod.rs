@@ -1,6 +1,17 @@
 /// import general checker
 use lemmeknow::Identifier;
 use once_cell::sync::OnceCell;
+use regex::Regex;
+use std::collections::HashSet;
+
+use crate::{
+    checkers::{checker_expression::CheckerExpression, crib_checker::Crib},
+    error::Error,
+    storage::SUPPORTED_LANGUAGES,
+};
+
+/// Config files and profiles, which set the same things as the command line flags
+pub mod file;
 
 /// Library input is the default API input
 /// The CLI turns its arguments into a LibraryInput struct
@@ -31,6 +42,50 @@ pub struct Config {
     pub api_mode: bool,
     /// Regex enables the user to search for a specific regex or crib
     pub regex: Option<String>,
+    /// The minimum confidence (0.0 to 1.0) a checker must have before Athena accepts its result
+    /// Each checker still applies its own rules first, so 0.0 accepts anything a checker identifies
+    pub confidence_threshold: f64,
+    /// The languages (ISO 639-1 codes) we look for plaintext in.
+    /// "en" turns on the English checkers, the others turn on the language checker.
+    pub languages: Vec<String>,
+    /// Extra words to treat as English, with the wordlist file they came from,
+    /// in the order the wordlists were given.
+    /// Load these with `storage::load_wordlists`.
+    pub wordlists: Vec<(String, HashSet<String>)>,
+    /// Extra CTF flag prefixes to look for, like "myctf" for `myctf{...}`.
+    /// Common ones such as flag, CTF and picoCTF are always checked.
+    pub flag_prefixes: Vec<String>,
+    /// Write the plaintext to this file instead of printing it.
+    /// Decoded binary files (PNG, ZIP etc) are written as their original bytes.
+    pub output_file: Option<String>,
+    /// Which checkers decide what is plaintext, like `regex AND english`.
+    /// None runs every checker (or just regex, if it's set).
+    pub checkers: Option<CheckerExpression>,
+    /// Known pieces of the plaintext. The crib checker looks for them
+    /// and key-recovering decoders like caesar use them to find the key.
+    pub cribs: Vec<Crib>,
+    /// Open the search tree explorer instead of searching by ourselves.
+    /// Only used by the CLI when built with the `tui` feature.
+    pub tui: bool,
+    /// Print every step of the decoding (its input, output, key and decoder)
+    /// and the checker which accepted the plaintext.
+    pub explain: bool,
+}
+
+impl Config {
+    /// Checks the config can be used before we search with it,
+    /// so a bad setting is an error up front rather than a checker failing mid-search.
+    /// # Errors
+    /// Returns an error if the regex isn't a valid regex.
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(regex) = &self.regex {
+            Regex::new(regex).map_err(|error| Error::InvalidRegex {
+                regex: regex.clone(),
+                error: error.to_string(),
+            })?;
+        }
+        Ok(())
+    }
 }
 
 /// Cell for storing global Config
@@ -47,16 +102,25 @@ pub fn get_config() -> &'static Config {
     CONFIG.get_or_init(Config::default)
 }
 
-/// Creates a default lemmeknow config
-const LEMMEKNOW_DEFAULT_CONFIG: Identifier = Identifier {
-    min_rarity: 0.0,
-    max_rarity: 0.0,
+/// Creates a default lemmeknow config.
+/// Patterns with a rarity below 0.1 are left out, they are so common they match too much ordinary text to be useful.
+pub const LEMMEKNOW_DEFAULT_CONFIG: Identifier = Identifier {
+    min_rarity: 0.1,
+    max_rarity: 1.0,
     tags: vec![],
     exclude_tags: vec![],
     file_support: false,
     boundaryless: false,
 };
 
+/// Every language we can detect, which is what we look for unless told otherwise
+pub fn default_languages() -> Vec<String> {
+    std::iter::once("en")
+        .chain(SUPPORTED_LANGUAGES.iter().map(|(code, _)| *code))
+        .map(String::from)
+        .collect()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
@@ -66,6 +130,15 @@ impl Default for Config {
             timeout: 5,
             api_mode: true,
             regex: None,
+            confidence_threshold: 0.0,
+            languages: default_languages(),
+            wordlists: Vec::new(),
+            flag_prefixes: vec![],
+            output_file: None,
+            checkers: None,
+            cribs: vec![],
+            tui: false,
+            explain: false,
         }
     }
 }
@@ -117,6 +117,28 @@ fn decode_one_char_a1z26(num_text: &str) -> Option<char> {
     }
 }
 
+/// Encodes each letter as its place in the alphabet, like `8-9 20-8-5-18-5` for `hi there`.
+/// Letters are joined with `-` and words with a space. Returns None if there's
+/// anything other than letters and whitespace, as A1Z26 can't encode it.
+pub(crate) fn encode_a1z26(text: &str) -> Option<String> {
+    let words: Option<Vec<String>> = text
+        .split_whitespace()
+        .map(|word| {
+            let letters: Option<Vec<String>> = word
+                .chars()
+                .map(|c| {
+                    c.is_ascii_alphabetic()
+                        .then(|| (c.to_ascii_uppercase() as u8 - b'A' + 1).to_string())
+                })
+                .collect();
+            letters.map(|letters| letters.join("-"))
+        })
+        .collect();
+    words
+        .map(|words| words.join(" "))
+        .filter(|encoded| !encoded.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,4 +284,13 @@ mod tests {
         let result = decoder.crack(",8-5:12,12;15\t23\r15\n18:,12-;4-", &get_athena_checker());
         assert_eq!(result.unencrypted_text.unwrap()[0], "HELLOWORLD");
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_a1z26("hi There").unwrap();
+        assert_eq!(encoded, "8-9 20-8-5-18-5");
+        // A1Z26 has no case or spaces, so we get back the letters in uppercase
+        assert_eq!(decode_a1z26(&encoded).unwrap(), "HITHERE");
+        assert!(encode_a1z26("hi!").is_none());
+    }
 }
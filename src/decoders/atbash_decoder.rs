@@ -58,7 +58,7 @@ impl Crack for Decoder<AtbashDecoder> {
 }
 
 /// Maps atbash to the alphabet
-fn atbash_to_alphabet(text: &str) -> String {
+pub(crate) fn atbash_to_alphabet(text: &str) -> String {
     text.chars()
         .map(|char| match char {
             letter @ 'a'..='z' => (b'a' + b'z' - letter as u8) as char,
@@ -70,7 +70,7 @@ fn atbash_to_alphabet(text: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::AtbashDecoder;
+    use super::{atbash_to_alphabet, AtbashDecoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -165,4 +165,12 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        // Atbash is its own inverse, so the decoder doubles as the encoder
+        let encoded = atbash_to_alphabet("Hello, World!");
+        assert_eq!(encoded, "Svool, Dliow!");
+        assert_eq!(atbash_to_alphabet(&encoded), "Hello, World!");
+    }
 }
@@ -3,14 +3,15 @@
 //! Call base32_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 
+use crate::checkers::file_type_checker::text_to_bytes;
 use crate::checkers::CheckerTypes;
-use crate::decoders::interface::check_string_success;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
 use super::interface::Decoder;
 
-use data_encoding::BASE32_NOPAD;
+use data_encoding::{BASE32, BASE32_NOPAD};
 use log::{debug, info, trace};
 
 /// The Base32 decoder, call:
@@ -50,7 +51,10 @@ impl Crack for Decoder<Base32Decoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
         trace!("Trying Base32 with text {:?}", text);
-        let decoded_text = decode_base32_no_error_handling(text);
+        let decoded_bytes = decode_base32_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().map(|bytes| {
+            bytes_to_string(bytes).unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned())
+        });
         let mut results = CrackResult::new(self, text.to_string());
 
         if decoded_text.is_none() {
@@ -69,6 +73,7 @@ impl Crack for Decoder<Base32Decoder> {
 
         let checker_result = checker.check(&decoded_text);
         results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
 
         results.update_checker(&checker_result);
 
@@ -85,20 +90,22 @@ impl Crack for Decoder<Base32Decoder> {
 }
 
 /// helper function
-fn decode_base32_no_error_handling(text: &str) -> Option<String> {
+fn decode_base32_no_error_handling(text: &str) -> Option<Vec<u8>> {
     // Strip all padding
     let text = text.replace('=', "");
     // Runs the code to decode base32
     // Doesn't perform error handling, call from_base32
-    if let Ok(decoded_text) = &BASE32_NOPAD.decode(text.as_bytes()) {
-        return Some(String::from_utf8_lossy(decoded_text).to_string());
-    }
-    None
+    BASE32_NOPAD.decode(text.as_bytes()).ok()
+}
+
+/// Encodes the text as padded base32
+pub(crate) fn encode_base32(text: &str) -> String {
+    BASE32.encode(&text_to_bytes(text))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Base32Decoder;
+    use super::{decode_base32_no_error_handling, encode_base32, Base32Decoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -186,4 +193,13 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_base32("hello world!");
+        assert_eq!(
+            decode_base32_no_error_handling(&encoded).unwrap(),
+            "hello world!".as_bytes()
+        );
+    }
 }
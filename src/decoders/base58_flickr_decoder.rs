@@ -3,8 +3,9 @@
 //! Call base58_flickr_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 
+use crate::checkers::file_type_checker::text_to_bytes;
 use crate::checkers::CheckerTypes;
-use crate::decoders::interface::check_string_success;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
@@ -49,7 +50,10 @@ impl Crack for Decoder<Base58FlickrDecoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
         trace!("Trying Base58_flickr with text {:?}", text);
-        let decoded_text = decode_base58_flickr_no_error_handling(text);
+        let decoded_bytes = decode_base58_flickr_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().map(|bytes| {
+            bytes_to_string(bytes).unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned())
+        });
         let mut results = CrackResult::new(self, text.to_string());
 
         if decoded_text.is_none() {
@@ -68,6 +72,7 @@ impl Crack for Decoder<Base58FlickrDecoder> {
 
         let checker_result = checker.check(&decoded_text);
         results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
 
         results.update_checker(&checker_result);
 
@@ -84,21 +89,30 @@ impl Crack for Decoder<Base58FlickrDecoder> {
 }
 
 /// helper function
-fn decode_base58_flickr_no_error_handling(text: &str) -> Option<String> {
+fn decode_base58_flickr_no_error_handling(text: &str) -> Option<Vec<u8>> {
     // Runs the code to decode base58_flickr
     // Doesn't perform error handling, call from_base58_flickr
     if let Ok(decoded_text) = bs58::decode(text)
         .with_alphabet(bs58::Alphabet::FLICKR)
         .into_vec()
     {
-        return Some(String::from_utf8_lossy(&decoded_text).to_string());
+        return Some(decoded_text);
     }
     None
 }
 
+/// Encodes the text as base58 with the Flickr alphabet
+pub(crate) fn encode_base58_flickr(text: &str) -> String {
+    bs58::encode(text_to_bytes(text))
+        .with_alphabet(bs58::Alphabet::FLICKR)
+        .into_string()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Base58FlickrDecoder;
+    use super::{
+        decode_base58_flickr_no_error_handling, encode_base58_flickr, Base58FlickrDecoder,
+    };
     use crate::{
         checkers::{
             athena::Athena,
@@ -175,4 +189,13 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_base58_flickr("hello world!");
+        assert_eq!(
+            decode_base58_flickr_no_error_handling(&encoded).unwrap(),
+            "hello world!".as_bytes()
+        );
+    }
 }
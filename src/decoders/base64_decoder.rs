@@ -3,8 +3,9 @@
 //! Call base64_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 
+use crate::checkers::file_type_checker::text_to_bytes;
 use crate::checkers::CheckerTypes;
-use crate::decoders::interface::check_string_success;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
 use base64::{engine::general_purpose, Engine as _};
 
 use super::crack_results::CrackResult;
@@ -50,7 +51,8 @@ impl Crack for Decoder<Base64Decoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
         trace!("Trying Base64 with text {:?}", text);
-        let decoded_text = decode_base64_no_error_handling(text);
+        let decoded_bytes = decode_base64_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().and_then(bytes_to_string);
         let mut results = CrackResult::new(self, text.to_string());
 
         if decoded_text.is_none() {
@@ -69,6 +71,7 @@ impl Crack for Decoder<Base64Decoder> {
 
         let checker_result = checker.check(&decoded_text);
         results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
 
         results.update_checker(&checker_result);
 
@@ -85,7 +88,7 @@ impl Crack for Decoder<Base64Decoder> {
 }
 
 /// helper function
-fn decode_base64_no_error_handling(text: &str) -> Option<String> {
+fn decode_base64_no_error_handling(text: &str) -> Option<Vec<u8>> {
     // Strip all padding
     let text = text.replace('=', "");
     // Runs the code to decode base64
@@ -93,12 +96,16 @@ fn decode_base64_no_error_handling(text: &str) -> Option<String> {
     general_purpose::STANDARD_NO_PAD
         .decode(text.as_bytes())
         .ok()
-        .map(|inner| String::from_utf8(inner).ok())?
+}
+
+/// Encodes the text as padded base64
+pub(crate) fn encode_base64(text: &str) -> String {
+    general_purpose::STANDARD.encode(text_to_bytes(text))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Base64Decoder;
+    use super::{decode_base64_no_error_handling, encode_base64, Base64Decoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -125,6 +132,18 @@ mod tests {
         assert_eq!(decoded_str[0], "hello world");
     }
 
+    #[test]
+    fn base64_decodes_binary_file_losslessly() {
+        // A PNG isn't UTF-8, but we keep it so the file type checker can find it
+        let base64_decoder = Decoder::<Base64Decoder>::new();
+        let result = base64_decoder.crack("iVBORw0KGgoAAAANSUhEUg==", &get_athena_checker());
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "\u{89}PNG\r\n\u{1a}\n\0\0\0\rIHDR"
+        );
+    }
+
     #[test]
     fn base64_decode_empty_string() {
         // Base64 returns an empty string, this is a valid base64 string
@@ -192,4 +211,13 @@ mod tests {
             assert_eq!(true, true);
         }
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_base64("hello world!");
+        assert_eq!(
+            decode_base64_no_error_handling(&encoded).unwrap(),
+            "hello world!".as_bytes()
+        );
+    }
 }
@@ -3,8 +3,9 @@
 //! Call base64_url_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 
+use crate::checkers::file_type_checker::text_to_bytes;
 use crate::checkers::CheckerTypes;
-use crate::decoders::interface::check_string_success;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
 use base64::{engine::general_purpose, Engine as _};
 
 use super::crack_results::CrackResult;
@@ -50,7 +51,8 @@ impl Crack for Decoder<Base64URLDecoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
         trace!("Trying base64_url with text {:?}", text);
-        let decoded_text = decode_base64_url_no_error_handling(text);
+        let decoded_bytes = decode_base64_url_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().and_then(bytes_to_string);
         let mut results = CrackResult::new(self, text.to_string());
 
         if decoded_text.is_none() {
@@ -69,6 +71,7 @@ impl Crack for Decoder<Base64URLDecoder> {
 
         let checker_result = checker.check(&decoded_text);
         results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
 
         results.update_checker(&checker_result);
 
@@ -85,7 +88,7 @@ impl Crack for Decoder<Base64URLDecoder> {
 }
 
 /// helper function
-fn decode_base64_url_no_error_handling(text: &str) -> Option<String> {
+fn decode_base64_url_no_error_handling(text: &str) -> Option<Vec<u8>> {
     // Strip all padding
     let text = text.replace('=', "");
     // Runs the code to decode base64_url
@@ -93,12 +96,16 @@ fn decode_base64_url_no_error_handling(text: &str) -> Option<String> {
     general_purpose::URL_SAFE_NO_PAD
         .decode(text.as_bytes())
         .ok()
-        .map(|inner| String::from_utf8(inner).ok())?
+}
+
+/// Encodes the text as URL safe base64, without padding like JWTs use
+pub(crate) fn encode_base64_url(text: &str) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(text_to_bytes(text))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Base64URLDecoder;
+    use super::{decode_base64_url_no_error_handling, encode_base64_url, Base64URLDecoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -209,4 +216,13 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_base64_url("hello world?>");
+        assert_eq!(
+            decode_base64_url_no_error_handling(&encoded).unwrap(),
+            "hello world?>".as_bytes()
+        );
+    }
 }
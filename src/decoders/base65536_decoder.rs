@@ -3,8 +3,9 @@
 //! Call base65536_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 
+use crate::checkers::file_type_checker::text_to_bytes;
 use crate::checkers::CheckerTypes;
-use crate::decoders::interface::check_string_success;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
@@ -49,7 +50,10 @@ impl Crack for Decoder<Base65536Decoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
         trace!("Trying base65536 with text {:?}", text);
-        let decoded_text: Option<String> = decode_base65536_no_error_handling(text);
+        let decoded_bytes = decode_base65536_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().map(|bytes| {
+            bytes_to_string(bytes).unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned())
+        });
 
         trace!("Decoded text for base65536: {:?}", decoded_text);
         let mut results = CrackResult::new(self, text.to_string());
@@ -70,6 +74,7 @@ impl Crack for Decoder<Base65536Decoder> {
 
         let checker_result = checker.check(&decoded_text);
         results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
 
         results.update_checker(&checker_result);
 
@@ -86,18 +91,23 @@ impl Crack for Decoder<Base65536Decoder> {
 }
 
 /// helper function
-fn decode_base65536_no_error_handling(text: &str) -> Option<String> {
+fn decode_base65536_no_error_handling(text: &str) -> Option<Vec<u8>> {
     // Runs the code to decode base65536
     // Doesn't perform error handling, call from_base65536
     if let Ok(decoded_text) = base65536::decode(text, false) {
-        return Some(String::from_utf8_lossy(&decoded_text).to_string());
+        return Some(decoded_text);
     }
     None
 }
 
+/// Encodes the text as base65536, on one line
+pub(crate) fn encode_base65536(text: &str) -> String {
+    base65536::encode(&text_to_bytes(text), base65536::WrapOptions::NoWrap)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Base65536Decoder;
+    use super::{decode_base65536_no_error_handling, encode_base65536, Base65536Decoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -159,4 +169,13 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_base65536("hello world!");
+        assert_eq!(
+            decode_base65536_no_error_handling(&encoded).unwrap(),
+            "hello world!".as_bytes()
+        );
+    }
 }
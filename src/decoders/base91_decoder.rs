@@ -3,8 +3,9 @@
 //! Call base91_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 
+use crate::checkers::file_type_checker::text_to_bytes;
 use crate::checkers::CheckerTypes;
-use crate::decoders::interface::check_string_success;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
@@ -49,7 +50,10 @@ impl Crack for Decoder<Base91Decoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
         trace!("Trying Base91 with text {:?}", text);
-        let decoded_text = decode_base91_no_error_handling(text);
+        let decoded_bytes = decode_base91_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().map(|bytes| {
+            bytes_to_string(bytes).unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned())
+        });
         let mut results = CrackResult::new(self, text.to_string());
 
         if decoded_text.is_none() {
@@ -68,6 +72,7 @@ impl Crack for Decoder<Base91Decoder> {
 
         let checker_result = checker.check(&decoded_text);
         results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
 
         results.update_checker(&checker_result);
 
@@ -84,16 +89,21 @@ impl Crack for Decoder<Base91Decoder> {
 }
 
 /// helper function
-fn decode_base91_no_error_handling(text: &str) -> Option<String> {
+fn decode_base91_no_error_handling(text: &str) -> Option<Vec<u8>> {
     // Runs the code to decode base91
     // Doesn't perform error handling, call from_base91
-    let decoded_text = base91::slice_decode(text.as_bytes());
-    return Some(String::from_utf8_lossy(&decoded_text).to_string());
+    Some(base91::slice_decode(text.as_bytes()))
+}
+
+/// Encodes the text as base91
+pub(crate) fn encode_base91(text: &str) -> String {
+    // base91 only uses printable ASCII, so this is never lossy
+    String::from_utf8_lossy(&base91::slice_encode(&text_to_bytes(text))).into_owned()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Base91Decoder;
+    use super::{decode_base91_no_error_handling, encode_base91, Base91Decoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -167,4 +177,13 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_base91("hello world!");
+        assert_eq!(
+            decode_base91_no_error_handling(&encoded).unwrap(),
+            "hello world!".as_bytes()
+        );
+    }
 }
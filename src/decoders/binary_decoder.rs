@@ -1,3 +1,4 @@
+use crate::checkers::file_type_checker::text_to_bytes;
 use crate::checkers::CheckerTypes;
 use crate::decoders::interface::check_string_success;
 
@@ -47,6 +48,7 @@ impl Crack for Decoder<BinaryDecoder> {
             if checker_result.is_identified {
                 info!("Found a match with binary bit {}", shift);
                 results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string()]);
+                results.unencrypted_bytes = Some(binary_to_bytes(text, shift));
                 results.update_checker(&checker_result);
                 return results;
             }
@@ -64,10 +66,10 @@ impl Crack for Decoder<BinaryDecoder> {
     }
 }
 
-/// Decodes binary to string
+/// Decodes binary to the bytes it encodes
 /// bit is the byte length
-fn binary_to_string(binary: &str, bit: u8) -> String {
-    let mut out = String::new();
+pub(crate) fn binary_to_bytes(binary: &str, bit: u8) -> Vec<u8> {
+    let mut out = Vec::new();
     let mut iter = binary.as_bytes().iter().filter_map(|byte| match byte {
         b'0' => Some(0),
         b'1' => Some(1),
@@ -79,16 +81,34 @@ fn binary_to_string(binary: &str, bit: u8) -> String {
             .take(usize::from(bit))
             .reduce(|acc, elem| (acc << 1) | elem);
         match byte {
-            Some(byte) => out.push(char::from(byte)),
+            Some(byte) => out.push(byte),
             None => break,
         }
     }
     out
 }
 
+/// Decodes binary to string, one char per byte
+/// bit is the byte length
+pub(crate) fn binary_to_string(binary: &str, bit: u8) -> String {
+    binary_to_bytes(binary, bit)
+        .into_iter()
+        .map(char::from)
+        .collect()
+}
+
+/// Encodes each byte of the text as 8 bits, with a space between bytes
+pub(crate) fn encode_binary(text: &str) -> String {
+    text_to_bytes(text)
+        .iter()
+        .map(|byte| format!("{byte:08b}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[cfg(test)]
 mod tests {
-    use super::BinaryDecoder;
+    use super::{binary_to_string, encode_binary, BinaryDecoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -177,4 +197,11 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_binary("hi!");
+        assert_eq!(encoded, "01101000 01101001 00100001");
+        assert_eq!(binary_to_string(&encoded, 8), "hi!");
+    }
 }
@@ -3,7 +3,9 @@
 //! Call caesar_decoder.crack to use. It returns option<String> and check with
 //! `result.is_some()` to see if it returned okay.
 
+use crate::checkers::crib_checker::Crib;
 use crate::checkers::CheckerTypes;
+use crate::config::get_config;
 use crate::decoders::interface::check_string_success;
 
 use super::crack_results::CrackResult;
@@ -53,9 +55,13 @@ impl Crack for Decoder<CaesarDecoder> {
         trace!("Trying Caesar Cipher with text {:?}", text);
         let mut results = CrackResult::new(self, text.to_string());
         let mut decoded_strings = Vec::new();
-        for shift in 1..=25 {
+        // Shifts that put the cribs in the plaintext are tried first
+        let crib_shifts = shifts_from_cribs(text, &get_config().cribs);
+        let other_shifts = (1..=25).filter(|shift| !crib_shifts.contains(shift));
+        for shift in crib_shifts.iter().copied().chain(other_shifts) {
             let decoded_text = caesar(text, shift);
             decoded_strings.push(decoded_text);
+            results.candidate_keys.push(inverse_shift(shift).to_string());
             let borrowed_decoded_text = &decoded_strings[decoded_strings.len() - 1];
             if !check_string_success(borrowed_decoded_text, text) {
                 info!(
@@ -69,6 +75,8 @@ impl Crack for Decoder<CaesarDecoder> {
             if checker_result.is_identified {
                 trace!("Found a match with caesar shift {}", shift);
                 results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string()]);
+                results.key = Some(inverse_shift(shift).to_string());
+                results.candidate_keys.clear();
                 results.update_checker(&checker_result);
                 return results;
             }
@@ -76,6 +84,17 @@ impl Crack for Decoder<CaesarDecoder> {
         results.unencrypted_text = Some(decoded_strings);
         results
     }
+    /// Decodes with the shift the text was encoded with, so a key of 13 undoes ROT13
+    fn crack_with_key(&self, text: &str, key: &str, checker: &CheckerTypes) -> Option<CrackResult> {
+        let shift = key.parse::<u8>().ok()? % 26;
+        let mut results = CrackResult::new(self, text.to_string());
+        let decoded_text = caesar(text, inverse_shift(shift));
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+        results.key = Some(shift.to_string());
+        results.update_checker(&checker_result);
+        Some(results)
+    }
     /// Gets all tags for this decoder
     fn get_tags(&self) -> &Vec<&str> {
         &self.tags
@@ -86,6 +105,12 @@ impl Crack for Decoder<CaesarDecoder> {
     }
 }
 
+/// The shift which undoes this one. Keys are the shift the text was encoded with,
+/// which is the opposite of the shift we decode with.
+fn inverse_shift(shift: u8) -> u8 {
+    (26 - shift % 26) % 26
+}
+
 /// Caesar cipher to rotate cipher text by shift and return an owned String.
 fn caesar(cipher: &str, shift: u8) -> String {
     cipher
@@ -102,6 +127,53 @@ fn caesar(cipher: &str, shift: u8) -> String {
         .collect()
 }
 
+/// The shifts which turn the ciphertext into text containing every crib.
+/// Each place a crib can line up with the ciphertext gives at most one shift.
+fn shifts_from_cribs(text: &str, cribs: &[Crib]) -> Vec<u8> {
+    let mut shifts: Option<Vec<u8>> = None;
+    for crib in cribs {
+        let crib_shifts: Vec<u8> = crib
+            .alignments(text)
+            .iter()
+            .filter_map(|pairs| shift_for_alignment(pairs))
+            .collect();
+        shifts = Some(match shifts {
+            None => crib_shifts,
+            Some(shifts) => shifts
+                .into_iter()
+                .filter(|shift| crib_shifts.contains(shift))
+                .collect(),
+        });
+    }
+    let mut shifts = shifts.unwrap_or_default();
+    shifts.sort_unstable();
+    shifts.dedup();
+    shifts
+}
+
+/// The one shift taking every ciphertext letter to its crib letter, if there is one.
+/// Anything that isn't a letter has to match exactly, as caesar leaves it alone.
+fn shift_for_alignment(pairs: &[(char, char)]) -> Option<u8> {
+    let mut shift = None;
+    for (cipher, plain) in pairs {
+        if cipher.is_ascii_lowercase() && plain.is_ascii_lowercase() {
+            let this_shift = (*plain as u8 + 26 - *cipher as u8) % 26;
+            if shift.is_some_and(|shift| shift != this_shift) {
+                return None;
+            }
+            shift = Some(this_shift);
+        } else if cipher != plain {
+            return None;
+        }
+    }
+    shift.filter(|shift| *shift != 0)
+}
+
+/// Encodes the text by shifting each letter forward, so a shift of 13 is ROT13
+pub(crate) fn encode_caesar(text: &str, shift: u8) -> String {
+    caesar(text, shift % 26)
+}
+
 #[cfg(test)]
 mod tests {
     use super::CaesarDecoder;
@@ -204,4 +276,30 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn shifts_from_cribs_finds_the_key() {
+        let crib: Crib = "hello".parse().unwrap();
+        assert_eq!(shifts_from_cribs("Uryyb, jbeyq", &[crib]), vec![13]);
+    }
+
+    #[test]
+    fn shifts_from_cribs_must_agree() {
+        let hello: Crib = "start:hello".parse().unwrap();
+        let world: Crib = "end:world".parse().unwrap();
+        assert_eq!(
+            shifts_from_cribs("uryyb jbeyq", &[hello.clone(), world]),
+            vec![13]
+        );
+        let wrong: Crib = "end:earth".parse().unwrap();
+        assert!(shifts_from_cribs("uryyb jbeyq", &[hello, wrong]).is_empty());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_caesar("Hello, World!", 3);
+        assert_eq!(encoded, "Khoor, Zruog!");
+        assert_eq!(caesar(&encoded, 23), "Hello, World!");
+        assert_eq!(encode_caesar("abc", 29), "def");
+    }
 }
@@ -107,9 +107,23 @@ fn decode_citrix_ctx1(text: &str) -> Result<String, Error> {
     String::from_utf8(result).map_err(|_| Error::InvalidUtf8)
 }
 
+/// Encodes the text with Citrix CTX1.
+/// Like Citrix we encode the text as UTF-16, so the decoder gets ASCII back exactly.
+pub(crate) fn encode_citrix_ctx1(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len() * 4);
+    let mut previous = 0u8;
+    for byte in text.encode_utf16().flat_map(u16::to_le_bytes) {
+        let value = byte ^ previous ^ 0xA5;
+        encoded.push(char::from(0x41 + (value >> 4)));
+        encoded.push(char::from(0x41 + (value & 0xF)));
+        previous = value;
+    }
+    encoded
+}
+
 #[cfg(test)]
 mod tests {
-    use super::CitrixCTX1Decoder;
+    use super::{decode_citrix_ctx1, encode_citrix_ctx1, CitrixCTX1Decoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -204,4 +218,10 @@ mod tests {
         let result = citrix_ctx1_decoder.crack("😂", &get_athena_checker());
         assert_eq!(result.unencrypted_text.unwrap()[0], "[*");
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_citrix_ctx1("hello world");
+        assert_eq!(decode_citrix_ctx1(&encoded).unwrap(), "hello world");
+    }
 }
@@ -14,6 +14,10 @@ pub struct CrackResult {
     /// Unencrypted text is what it looks like after.
     /// if decoder failed, this will be None
     pub unencrypted_text: Option<Vec<String>>,
+    /// The bytes the text was decoded to, for decoders like base64 which decode bytes.
+    /// Binary files are kept in the text as one char per byte, which can't always
+    /// be told apart from UTF-8, so `-o` writes these bytes instead.
+    pub unencrypted_bytes: Option<Vec<u8>>,
     /// Decoder is the function we used to decode the text
     pub decoder: &'static str,
     /// Checker which identified the text
@@ -21,11 +25,19 @@ pub struct CrackResult {
     /// Description is a short description of the checker
     pub checker_description: &'static str,
     /// Key is optional as decoders do not use keys.
-    pub key: Option<&'static str>,
+    /// Written the way recipes take it, like `13` for caesar.
+    pub key: Option<String>,
+    /// The key each of the unencrypted texts was decoded with, for decoders like
+    /// caesar which try every key. Empty if there's one text or no keys.
+    pub candidate_keys: Vec<String>,
     /// Description is a short description of the decoder
     pub description: &'static str,
     /// Link is a link to more info about the decoder
     pub link: &'static str,
+    /// How confident the checker was that the output is plaintext
+    pub confidence: f64,
+    /// What the checker found in the output, like a CTF flag
+    pub matched: Option<String>,
 }
 
 impl CrackResult {
@@ -35,12 +47,16 @@ impl CrackResult {
             success: false,
             encrypted_text: text,
             unencrypted_text: None,
+            unencrypted_bytes: None,
             decoder: decoder_used.name,
             checker_name: "",
             checker_description: "",
             key: None,
+            candidate_keys: Vec::new(),
             description: decoder_used.description,
             link: decoder_used.link,
+            confidence: 0.0,
+            matched: None,
         }
     }
 
@@ -49,5 +65,26 @@ impl CrackResult {
         self.checker_name = checker_result.checker_name;
         self.checker_description = checker_result.checker_description;
         self.success = checker_result.is_identified;
+        self.confidence = checker_result.confidence;
+        self.matched = checker_result.matched.clone();
+    }
+
+    /// Splits this into each of its texts, with a result of its own which has the key
+    /// that text was decoded with. Paths are made of these, so they say which text was followed.
+    pub fn into_candidates(mut self) -> Vec<(String, CrackResult)> {
+        let texts = self.unencrypted_text.take().unwrap_or_default();
+        let keys = std::mem::take(&mut self.candidate_keys);
+        texts
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let mut candidate = self.clone();
+                candidate.unencrypted_text = Some(vec![text.clone()]);
+                if let Some(key) = keys.get(index) {
+                    candidate.key = Some(key.clone());
+                }
+                (text, candidate)
+            })
+            .collect()
     }
 }
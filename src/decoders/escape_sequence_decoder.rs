@@ -0,0 +1,331 @@
+//! Decode C / JavaScript / Python style backslash escape sequences
+//! Performs error handling and returns a string
+//! Call escape_sequence_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::CheckerTypes;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{debug, info, trace};
+
+/// The escape sequence decoder, call:
+/// `let escape_sequence_decoder = Decoder::<EscapeSequenceDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = escape_sequence_decoder.crack(input)` to decode a string with escape sequences
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::escape_sequence_decoder::{EscapeSequenceDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_escape_sequence = Decoder::<EscapeSequenceDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// let result = decode_escape_sequence.crack(r"\x41B\103 D\u{45}", &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "ABC DE");
+/// ```
+pub struct EscapeSequenceDecoder;
+
+impl Crack for Decoder<EscapeSequenceDecoder> {
+    fn new() -> Decoder<EscapeSequenceDecoder> {
+        Decoder {
+            name: "Escape Sequence",
+            description: "An escape sequence is a combination of characters that has a meaning other than the literal characters contained therein. In C, JavaScript and Python string literals a backslash introduces a hex (\\x41), octal (\\101) or unicode (\\u0041) escape, or a control character such as \\n.",
+            link: "https://en.wikipedia.org/wiki/Escape_sequences_in_C",
+            tags: vec!["escape", "programming", "decoder"],
+            popularity: 0.5,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying escape sequences with text {:?}", text);
+        let decoded_text = decode_escape_sequences_no_error_handling(text);
+        let mut results = CrackResult::new(self, text.to_string());
+
+        if decoded_text.is_none() {
+            debug!("Failed to decode escape sequences because EscapeSequenceDecoder::decode_escape_sequences_no_error_handling returned None");
+            return results;
+        }
+
+        let decoded_text = decoded_text.unwrap();
+        if !check_string_success(&decoded_text, text) {
+            info!(
+                "Failed to decode escape sequences because check_string_success returned false on string {}",
+                decoded_text
+            );
+            return results;
+        }
+
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+
+        results.update_checker(&checker_result);
+
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// helper function
+/// `\x` and octal escapes produce raw bytes (so `\xe2\x9c\x93` is a UTF-8 ✓),
+/// unicode escapes produce characters. Unknown escapes are kept as-is like Python does.
+/// Returns None if there are no escapes or the resulting bytes are not UTF-8.
+fn decode_escape_sequences_no_error_handling(text: &str) -> Option<String> {
+    if !text.contains('\\') {
+        return None;
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut out: Vec<u8> = Vec::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '\\' || i + 1 == chars.len() {
+            push_char(&mut out, chars[i]);
+            i += 1;
+            continue;
+        }
+        let escape = chars[i + 1];
+        i += 2;
+        match escape {
+            'n' => out.push(b'\n'),
+            't' => out.push(b'\t'),
+            'r' => out.push(b'\r'),
+            'a' => out.push(0x07),
+            'b' => out.push(0x08),
+            'f' => out.push(0x0c),
+            'v' => out.push(0x0b),
+            'e' => out.push(0x1b),
+            '\\' | '\'' | '"' | '?' | '/' => push_char(&mut out, escape),
+            'x' => {
+                // \xHH, exactly two hex digits
+                let (value, used) = read_digits(&chars[i..], 16, 2, 2)?;
+                out.push(value as u8);
+                i += used;
+            }
+            '0'..='7' => {
+                // \N, \NN or \NNN octal, the first digit is the escape itself
+                let (value, used) = read_digits(&chars[i - 1..], 8, 1, 3)?;
+                out.push(u8::try_from(value).ok()?);
+                i += used - 1;
+            }
+            'u' if chars.get(i) == Some(&'{') => {
+                // \u{H...} as used in JavaScript and Rust
+                let close = chars[i..].iter().position(|c| *c == '}')?;
+                let (value, used) = read_digits(&chars[i + 1..i + close], 16, 1, 6)?;
+                if used != close - 1 {
+                    return None;
+                }
+                push_char(&mut out, char::from_u32(value)?);
+                i += close + 1;
+            }
+            'u' => {
+                // \uHHHH, possibly a UTF-16 surrogate pair as JavaScript and JSON write them
+                let (high, used) = read_digits(&chars[i..], 16, 4, 4)?;
+                i += used;
+                let code_point = if (0xD800..0xDC00).contains(&high) {
+                    if chars.get(i) != Some(&'\\') || chars.get(i + 1) != Some(&'u') {
+                        return None;
+                    }
+                    let (low, used) = read_digits(&chars[i + 2..], 16, 4, 4)?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return None;
+                    }
+                    i += used + 2;
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+                push_char(&mut out, char::from_u32(code_point)?);
+            }
+            'U' => {
+                // \UHHHHHHHH as used in Python
+                let (value, used) = read_digits(&chars[i..], 16, 8, 8)?;
+                push_char(&mut out, char::from_u32(value)?);
+                i += used;
+            }
+            _ => {
+                push_char(&mut out, '\\');
+                push_char(&mut out, escape);
+            }
+        }
+    }
+
+    String::from_utf8(out).ok()
+}
+
+/// Appends the UTF-8 encoding of a char to the output bytes
+fn push_char(out: &mut Vec<u8>, c: char) {
+    let mut buffer = [0; 4];
+    out.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+}
+
+/// Reads between `min` and `max` digits of the given radix from the start of `chars`
+/// Returns the value and how many chars were used, or None if there were too few digits
+fn read_digits(chars: &[char], radix: u32, min: usize, max: usize) -> Option<(u32, usize)> {
+    let mut value: u32 = 0;
+    let mut used = 0;
+    for c in chars.iter().take(max) {
+        match c.to_digit(radix) {
+            Some(digit) => {
+                value = value * radix + digit;
+                used += 1;
+            }
+            None => break,
+        }
+    }
+    if used < min {
+        return None;
+    }
+    Some((value, used))
+}
+
+/// Encodes every byte of the text as a `\xHH` escape sequence
+pub(crate) fn encode_escape_sequences(text: &str) -> String {
+    text.bytes().map(|byte| format!("\\x{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_escape_sequences_no_error_handling, encode_escape_sequences, EscapeSequenceDecoder,
+    };
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    #[test]
+    fn escape_sequence_decodes_hex_and_octal() {
+        // This tests if C style hex and octal escapes decode successfully
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder.crack(
+            r"\x68\x74\164\160\163://www.google.com",
+            &get_athena_checker(),
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://www.google.com"
+        );
+    }
+
+    #[test]
+    fn escape_sequence_decodes_utf8_bytes() {
+        // Consecutive \x escapes are treated as bytes, so multi-byte UTF-8 works
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder.crack(r"caf\xc3\xa9 \xe2\x9c\x93", &get_athena_checker());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "café ✓");
+    }
+
+    #[test]
+    fn escape_sequence_decodes_unicode_escapes() {
+        // JavaScript surrogate pairs, \u{...} and Python \U escapes
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder.crack(
+            r"\ud83d\ude02 \u{1F602} \U0001F602\tdone\n",
+            &get_athena_checker(),
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "😂 😂 😂\tdone\n");
+    }
+
+    #[test]
+    fn escape_sequence_handles_invalid_hex_escape() {
+        // \x needs two hex digits
+        // It should return None
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder
+            .crack(r"\xZZ hello", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn escape_sequence_handles_lone_surrogate() {
+        // A high surrogate without its low half is not valid
+        // It should return None
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder
+            .crack(r"\ud83d hello", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn escape_sequence_handles_panics() {
+        // This tests if escape sequence can handle panics
+        // It should return None
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn escape_sequence_handles_panic_if_trailing_backslash() {
+        // A lone backslash at the end is kept, so nothing changes
+        // It should return None
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder
+            .crack(r"hello\", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn escape_sequence_handles_panic_if_empty_string() {
+        // This tests if escape sequence can handle an empty string
+        // It should return None
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn escape_sequence_handles_panic_if_emoji() {
+        // This tests if escape sequence can handle an emoji
+        // It should return None
+        let decoder = Decoder::<EscapeSequenceDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_escape_sequences("hé!");
+        assert_eq!(encoded, "\\x68\\xc3\\xa9\\x21");
+        assert_eq!(
+            decode_escape_sequences_no_error_handling(&encoded).unwrap(),
+            "hé!"
+        );
+    }
+}
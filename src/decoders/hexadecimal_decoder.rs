@@ -1,5 +1,6 @@
+use crate::checkers::file_type_checker::text_to_bytes;
 use crate::checkers::CheckerTypes;
-use crate::decoders::interface::check_string_success;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
 
 use super::crack_results::CrackResult;
 use super::interface::Crack;
@@ -15,8 +16,6 @@ pub struct HexadecimalDecoder;
 enum Error {
     /// Error when the input is not divisible by 2
     InvalidLength,
-    /// Error if the result isn't UTF-8
-    InvalidUtf8,
 }
 
 impl Crack for Decoder<HexadecimalDecoder> {
@@ -36,7 +35,11 @@ impl Crack for Decoder<HexadecimalDecoder> {
     /// Else the Option returns nothing and the error is logged in Trace
     fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
         trace!("Trying hexadecimal with text {:?}", text);
-        let decoded_text: Result<String, Error> = hexadecimal_to_string(text);
+        let decoded_bytes = hexadecimal_to_bytes(text);
+        // Bytes which aren't text or a file are still decoded, one char per byte
+        let decoded_text: Result<String, &Error> = decoded_bytes.as_deref().map(|bytes| {
+            bytes_to_string(bytes).unwrap_or_else(|| bytes.iter().copied().map(char::from).collect())
+        });
         let mut results = CrackResult::new(self, text.to_string());
 
         if decoded_text.is_err() {
@@ -58,6 +61,7 @@ impl Crack for Decoder<HexadecimalDecoder> {
 
         let checker_result = checker.check(&decoded_text);
         results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes.ok();
 
         results.update_checker(&checker_result);
 
@@ -73,8 +77,8 @@ impl Crack for Decoder<HexadecimalDecoder> {
     }
 }
 
-/// Decodes hexadecimal to string
-fn hexadecimal_to_string(hex: &str) -> Result<String, Error> {
+/// Decodes hexadecimal to the bytes it encodes
+fn hexadecimal_to_bytes(hex: &str) -> Result<Vec<u8>, Error> {
     // Remove "0x" delimiters
     let hex = hex.replace("0x", "");
     // Remove all non-hexadecimal characters from the string
@@ -89,19 +93,24 @@ fn hexadecimal_to_string(hex: &str) -> Result<String, Error> {
     }
 
     // Iterate over the vector of bytes in pairs
-    let mut result = String::new();
-    for pair in bytes.chunks(2) {
-        // Parse the pair of bytes as a hexadecimal number and push the corresponding
-        // ASCII character to the result string
-        result.push(u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap() as char);
-    }
+    // and parse each pair as a hexadecimal number
+    Ok(bytes
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+        .collect())
+}
 
-    String::from_utf8(result.into()).map_err(|_| Error::InvalidUtf8)
+/// Encodes each byte of the text as two lowercase hexadecimal digits
+pub(crate) fn encode_hexadecimal(text: &str) -> String {
+    text_to_bytes(text)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::HexadecimalDecoder;
+    use super::{encode_hexadecimal, hexadecimal_to_bytes, HexadecimalDecoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -235,4 +244,10 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_hexadecimal("hello world!");
+        assert_eq!(hexadecimal_to_bytes(&encoded).unwrap(), b"hello world!");
+    }
 }
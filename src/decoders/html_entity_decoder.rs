@@ -0,0 +1,198 @@
+//! Decode HTML / XML character entities
+//! Performs error handling and returns a string
+//! Call html_entity_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::CheckerTypes;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{debug, info, trace};
+
+/// The HTML entity decoder, call:
+/// `let html_entity_decoder = Decoder::<HTMLEntityDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = html_entity_decoder.crack(input)` to decode a string containing HTML entities
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::html_entity_decoder::{HTMLEntityDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_html_entity = Decoder::<HTMLEntityDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// let result = decode_html_entity.crack("&#x48;ello &amp; goodbye &copy;", &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "Hello & goodbye ©");
+/// ```
+pub struct HTMLEntityDecoder;
+
+impl Crack for Decoder<HTMLEntityDecoder> {
+    fn new() -> Decoder<HTMLEntityDecoder> {
+        Decoder {
+            name: "HTML Entity",
+            description: "In SGML, HTML and XML documents, a character entity reference is a named (&amp;) or numeric (&#38; or &#x26;) reference to a character. They are used to represent characters which are reserved by the markup or which cannot be typed directly.",
+            link: "https://en.wikipedia.org/wiki/List_of_XML_and_HTML_character_entity_references",
+            tags: vec!["html", "xml", "web", "decoder"],
+            popularity: 0.6,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying HTML entities with text {:?}", text);
+        let decoded_text = decode_html_entities_no_error_handling(text);
+        let mut results = CrackResult::new(self, text.to_string());
+
+        if decoded_text.is_none() {
+            debug!("Failed to decode HTML entities because HTMLEntityDecoder::decode_html_entities_no_error_handling returned None");
+            return results;
+        }
+
+        let decoded_text = decoded_text.unwrap();
+        if !check_string_success(&decoded_text, text) {
+            info!(
+                "Failed to decode HTML entities because check_string_success returned false on string {}",
+                decoded_text
+            );
+            return results;
+        }
+
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+
+        results.update_checker(&checker_result);
+
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// helper function
+fn decode_html_entities_no_error_handling(text: &str) -> Option<String> {
+    // Every entity starts with `&` and ends with `;`, no point decoding without both
+    if !text.contains('&') || !text.contains(';') {
+        return None;
+    }
+    // Uses the full HTML5 named entity table, plus decimal and hex references
+    Some(html_escape::decode_html_entities(text).into_owned())
+}
+
+/// Encodes every character of the text as a decimal HTML entity, like `&#104;` for `h`
+pub(crate) fn encode_html_entities(text: &str) -> String {
+    text.chars()
+        .map(|c| format!("&#{};", u32::from(c)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_html_entities_no_error_handling, encode_html_entities, HTMLEntityDecoder};
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    #[test]
+    fn html_entity_decodes_numeric_entities() {
+        // This tests if decimal and hex entities decode successfully
+        let decoder = Decoder::<HTMLEntityDecoder>::new();
+        let result = decoder.crack(
+            "&#104;&#116;&#116;&#112;&#x73;&#x3A;//www.google.com",
+            &get_athena_checker(),
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://www.google.com"
+        );
+    }
+
+    #[test]
+    fn html_entity_decodes_named_entities() {
+        // Named entities come from the full HTML5 table, not just the XML five
+        let decoder = Decoder::<HTMLEntityDecoder>::new();
+        let result = decoder.crack(
+            "&lt;p&gt;caf&eacute; &amp; cr&egrave;me &hearts;&lt;/p&gt;",
+            &get_athena_checker(),
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "<p>café & crème ♥</p>");
+    }
+
+    #[test]
+    fn html_entity_handles_unknown_entities() {
+        // Unknown entities are left alone, so nothing changes
+        // It should return None
+        let decoder = Decoder::<HTMLEntityDecoder>::new();
+        let result = decoder
+            .crack("&notarealentity; hello", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn html_entity_handles_panics() {
+        // This tests if HTML entity can handle panics
+        // It should return None
+        let decoder = Decoder::<HTMLEntityDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn html_entity_handles_panic_if_empty_string() {
+        // This tests if HTML entity can handle an empty string
+        // It should return None
+        let decoder = Decoder::<HTMLEntityDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn html_entity_handles_panic_if_emoji() {
+        // This tests if HTML entity can handle an emoji
+        // It should return None
+        let decoder = Decoder::<HTMLEntityDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_html_entities("<hé>");
+        assert_eq!(encoded, "&#60;&#104;&#233;&#62;");
+        assert_eq!(
+            decode_html_entities_no_error_handling(&encoded).unwrap(),
+            "<hé>"
+        );
+    }
+}
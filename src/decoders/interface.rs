@@ -1,3 +1,4 @@
+use crate::checkers::file_type_checker::detect_file_type;
 use crate::checkers::CheckerTypes;
 
 use super::crack_results::CrackResult;
@@ -39,11 +40,34 @@ impl Default for Decoder<DefaultDecoder> {
     }
 }
 
+/// What a decoder is, shown by `ares list decoders` and `ares explain`.
+/// Every `Decoder` gets this for free, so decoders don't write a getter for each field.
+pub trait Describe {
+    /// Get the description of the current decoder
+    fn get_description(&self) -> &'static str;
+    /// Get the link to read more about the current decoder
+    fn get_link(&self) -> &'static str;
+    /// Get the popularity of the current decoder
+    fn get_popularity(&self) -> f32;
+}
+
+impl<Type> Describe for Decoder<Type> {
+    fn get_description(&self) -> &'static str {
+        self.description
+    }
+    fn get_link(&self) -> &'static str {
+        self.link
+    }
+    fn get_popularity(&self) -> f32 {
+        self.popularity
+    }
+}
+
 /// All decoders will share the same Crack trait
 /// Which let's us put them into a vector and iterate over them,
 /// Running `.crack()` on each of them.
 /// Relevant docs: https://docs.rs/crack/0.3.0/crack/trait.Crack.html
-pub trait Crack {
+pub trait Crack: Describe {
     /// This function generates a new crack trait
     fn new() -> Self
     where
@@ -54,6 +78,16 @@ pub trait Crack {
     fn get_tags(&self) -> &Vec<&str>;
     /// Get the nam of the current decoder
     fn get_name(&self) -> &str;
+    /// Decodes the text with a known key instead of trying every key, used by recipes.
+    /// Returns None if the decoder doesn't take a key or doesn't understand this one.
+    fn crack_with_key(
+        &self,
+        _text: &str,
+        _key: &str,
+        _checker: &CheckerTypes,
+    ) -> Option<CrackResult> {
+        None
+    }
 }
 
 /// Returns a boolean of True if the string is successfully changed
@@ -67,3 +101,19 @@ pub fn check_string_success(decoded_text: &str, original_text: &str) -> bool {
     }
     false
 }
+
+/// Decoders pass strings to each other, but a layer can decode to a binary file like a PNG.
+/// Returns the bytes as UTF-8 text if they are valid UTF-8. If not, but they start with a
+/// file signature we know, they are kept as one char per byte (Latin-1) so nothing is lost
+/// and the file type checker can recognise them. Anything else is None.
+/// The decoder should keep the bytes too, as `CrackResult::unencrypted_bytes`,
+/// because the text can't always be turned back into them.
+pub fn bytes_to_string(bytes: &[u8]) -> Option<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text.to_string()),
+        Err(_) => {
+            detect_file_type(bytes)?;
+            Some(bytes.iter().copied().map(char::from).collect())
+        }
+    }
+}
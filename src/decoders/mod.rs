@@ -1,63 +1,93 @@
 //! This module contains all the code for decoders
 //! Think of a decoder as a decryption method that doesn't require a key
 //! The `interface.rs` defines what each decoder looks like.
-//! Once you have made a decoder you need to add it to the filtration system's
-//! mod.rs file
-//! you will also need to make it a public module in this file.
+//! Once you have made a decoder, add its module and struct to `register_decoders!` below.
+//! That's all, the registry builds it and the searcher, CLI and recipes all use the registry.
 
-/// The a1z26_decoder module decodes A1Z26
-pub mod a1z26_decoder;
-/// The atbash_decoder module decodes atbash
-pub mod atbash_decoder;
-/// The base32_decoder module decodes base32
-pub mod base32_decoder;
-/// The base58_bitcoin_decoder module decodes base58 bitcoin
-pub mod base58_bitcoin_decoder;
-/// The base58_monero_decoder module decodes base58 monero
-pub mod base58_monero_decoder;
-/// The binary_decoder module decodes binary
-pub mod binary_decoder;
-/// The hexadecimal_decoder module decodes hexadecimal
-pub mod hexadecimal_decoder;
+use self::interface::{Crack, Decoder};
 
-/// The base58_ripple_decoder module decodes base58 ripple
-pub mod base58_ripple_decoder;
-
-/// The base58_flickr decoder module decodes base58 flickr
-pub mod base58_flickr_decoder;
-
-/// The base64_decoder module decodes base64
-/// It is public as we use it in some tests.
-pub mod base64_decoder;
-/// The base64_url_decoder module decodes base64 url
-pub mod base64_url_decoder;
-/// The base65536 module decodes base65536
-pub mod base65536_decoder;
-/// The base91_decoder module decodes base91
-pub mod base91_decoder;
-/// The citrix_ctx1_decoder module decodes citrix ctx1
-pub mod citrix_ctx1_decoder;
 /// The crack_results module defines the CrackResult
 /// Each and every decoder return same CrackResult
 pub mod crack_results;
-/// The url_decoder module decodes url
-pub mod url_decoder;
 
 /// The interface module defines the interface for decoders
 /// Each and every decoder has the same struct & traits
 pub mod interface;
 
-/// The reverse_decoder module decodes reverse text
-/// Stac -> Cats
-/// It is public as we use it in some tests.
-pub mod reverse_decoder;
-
-/// The morse_code module decodes morse code
-/// It is public as we use it in some tests.
-pub mod morse_code;
+/// Declares each decoder's module and registers its decoder.
+/// The order here is the order the searcher runs them in.
+macro_rules! register_decoders {
+    ($($(#[$doc:meta])* $module:ident::$decoder:ident,)*) => {
+        $($(#[$doc])* pub mod $module;)*
 
-/// For the caesar cipher decoder
-pub mod caesar_decoder;
+        /// Builds every registered decoder, the registry calls this once
+        pub(crate) fn registered_decoders() -> Vec<Box<dyn Crack + Send + Sync>> {
+            vec![$(Box::new(Decoder::<$module::$decoder>::new())),*]
+        }
+    };
+}
 
-/// For the railfence cipher decoder
-pub mod railfence_decoder;
+register_decoders! {
+    /// The reverse_decoder module decodes reverse text
+    /// Stac -> Cats
+    /// It is public as we use it in some tests.
+    reverse_decoder::ReverseDecoder,
+    /// The base64_decoder module decodes base64
+    /// It is public as we use it in some tests.
+    base64_decoder::Base64Decoder,
+    /// The base58_bitcoin_decoder module decodes base58 bitcoin
+    base58_bitcoin_decoder::Base58BitcoinDecoder,
+    /// The base58_monero_decoder module decodes base58 monero
+    base58_monero_decoder::Base58MoneroDecoder,
+    /// The base58_ripple_decoder module decodes base58 ripple
+    base58_ripple_decoder::Base58RippleDecoder,
+    /// The base58_flickr decoder module decodes base58 flickr
+    base58_flickr_decoder::Base58FlickrDecoder,
+    /// The base91_decoder module decodes base91
+    base91_decoder::Base91Decoder,
+    /// The base65536 module decodes base65536
+    base65536_decoder::Base65536Decoder,
+    /// The binary_decoder module decodes binary
+    binary_decoder::BinaryDecoder,
+    /// The hexadecimal_decoder module decodes hexadecimal
+    hexadecimal_decoder::HexadecimalDecoder,
+    /// The base32_decoder module decodes base32
+    base32_decoder::Base32Decoder,
+    /// The morse_code module decodes morse code
+    /// It is public as we use it in some tests.
+    morse_code::MorseCodeDecoder,
+    /// The atbash_decoder module decodes atbash
+    atbash_decoder::AtbashDecoder,
+    /// For the caesar cipher decoder
+    caesar_decoder::CaesarDecoder,
+    /// For the railfence cipher decoder
+    railfence_decoder::RailfenceDecoder,
+    /// The citrix_ctx1_decoder module decodes citrix ctx1
+    citrix_ctx1_decoder::CitrixCTX1Decoder,
+    /// The url_decoder module decodes url
+    url_decoder::URLDecoder,
+    /// The quoted_printable_decoder module decodes quoted-printable
+    quoted_printable_decoder::QuotedPrintableDecoder,
+    /// The html_entity_decoder module decodes HTML and XML entities
+    html_entity_decoder::HTMLEntityDecoder,
+    /// The escape_sequence_decoder module decodes C, JavaScript and Python escape sequences
+    escape_sequence_decoder::EscapeSequenceDecoder,
+    /// The punycode_decoder module decodes punycode (IDNA) hostnames
+    punycode_decoder::PunycodeDecoder,
+    /// The base64_url_decoder module decodes base64 url
+    base64_url_decoder::Base64URLDecoder,
+    /// The jwt_decoder module decodes JSON Web Tokens
+    jwt_decoder::JWTDecoder,
+    /// The a1z26_decoder module decodes A1Z26
+    a1z26_decoder::A1Z26Decoder,
+    /// The uuencode_decoder module decodes uuencode
+    uuencode_decoder::UUEncodeDecoder,
+    /// The xxencode_decoder module decodes xxencode
+    xxencode_decoder::XXEncodeDecoder,
+    /// The yenc_decoder module decodes yEnc
+    yenc_decoder::YEncDecoder,
+    /// The zero_width_decoder module extracts messages hidden with zero-width characters
+    zero_width_decoder::ZeroWidthDecoder,
+    /// The whitespace_decoder module extracts messages hidden in trailing whitespace
+    whitespace_decoder::WhitespaceDecoder,
+}
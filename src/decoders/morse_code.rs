@@ -150,6 +150,80 @@ fn morse_to_alphanumeric(text: &str) -> Option<&str> {
     Some(result)
 }
 
+/// Encodes the text as morse code, with a space between letters and ` / ` between words.
+/// Returns None if a character has no morse code.
+pub(crate) fn encode_morse(text: &str) -> Option<String> {
+    let words: Option<Vec<String>> = text
+        .split_whitespace()
+        .map(|word| {
+            let letters: Option<Vec<&str>> = word.chars().map(alphanumeric_to_morse).collect();
+            letters.map(|letters| letters.join(" "))
+        })
+        .collect();
+    words
+        .map(|words| words.join(" / "))
+        .filter(|encoded| !encoded.is_empty())
+}
+
+/// Maps a character to its morse code, the reverse of `morse_to_alphanumeric`
+fn alphanumeric_to_morse(c: char) -> Option<&'static str> {
+    let code = match c.to_ascii_uppercase() {
+        'A' => ".-",
+        'B' => "-...",
+        'C' => "-.-.",
+        'D' => "-..",
+        'E' => ".",
+        'F' => "..-.",
+        'G' => "--.",
+        'H' => "....",
+        'I' => "..",
+        'J' => ".---",
+        'K' => "-.-",
+        'L' => ".-..",
+        'M' => "--",
+        'N' => "-.",
+        'O' => "---",
+        'P' => ".--.",
+        'Q' => "--.-",
+        'R' => ".-.",
+        'S' => "...",
+        'T' => "-",
+        'U' => "..-",
+        'V' => "...-",
+        'W' => ".--",
+        'X' => "-..-",
+        'Y' => "-.--",
+        'Z' => "--..",
+        '1' => ".----",
+        '2' => "..---",
+        '3' => "...--",
+        '4' => "....-",
+        '5' => ".....",
+        '6' => "-....",
+        '7' => "--...",
+        '8' => "---..",
+        '9' => "----.",
+        '0' => "-----",
+        '&' => ".-...",
+        '@' => ".--.-.",
+        ':' => "---...",
+        ',' => "--..--",
+        '.' => ".-.-.-",
+        '\'' => ".----.",
+        '"' => ".-..-.",
+        '?' => "..--..",
+        '/' => "-..-.",
+        '=' => "-...-",
+        '+' => ".-.-.",
+        '-' => "-....-",
+        '(' => "-.--.",
+        ')' => "-.--.-",
+        '!' => "-.-.--",
+        _ => return None,
+    };
+    Some(code)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,4 +375,23 @@ mod tests {
         );
         assert_eq!(result.unencrypted_text.unwrap()[0], "HELLO WORLD!");
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_morse("SOS, send help!").unwrap();
+        assert_eq!(
+            encoded,
+            "... --- ... --..-- / ... . -. -.. / .... . .-.. .--. -.-.--"
+        );
+        let decoded: String = normalise_morse_string(&encoded)
+            .split(' ')
+            .map(morse_to_alphanumeric)
+            .collect::<Option<String>>()
+            .unwrap();
+        assert_eq!(
+            decoded.split_whitespace().collect::<Vec<_>>().join(" "),
+            "SOS, SEND HELP!"
+        );
+        assert!(encode_morse("~").is_none());
+    }
 }
@@ -0,0 +1,439 @@
+//! Decode punycode (IDNA) labels in a hostname or URL
+//! Performs error handling and returns a string
+//! Call punycode_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::CheckerTypes;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{debug, info, trace};
+
+/// The punycode decoder, call:
+/// `let punycode_decoder = Decoder::<PunycodeDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = punycode_decoder.crack(input)` to decode the `xn--` labels of a hostname or URL
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::punycode_decoder::{PunycodeDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_punycode = Decoder::<PunycodeDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// let result = decode_punycode.crack("https://www.xn--bcher-kva.example/path?q=1", &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "https://www.bücher.example/path?q=1");
+/// ```
+pub struct PunycodeDecoder;
+
+impl Crack for Decoder<PunycodeDecoder> {
+    fn new() -> Decoder<PunycodeDecoder> {
+        Decoder {
+            name: "Punycode",
+            description: "Punycode is a representation of Unicode with the limited ASCII character subset used for Internet hostnames. Internationalized domain names (IDNA) store each non-ASCII label as punycode with an xn-- prefix, which is often abused for lookalike phishing domains.",
+            link: "https://en.wikipedia.org/wiki/Punycode",
+            tags: vec!["punycode", "idna", "url", "web", "decoder"],
+            popularity: 0.4,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying punycode with text {:?}", text);
+        let decoded_text = decode_punycode_labels_no_error_handling(text);
+        let mut results = CrackResult::new(self, text.to_string());
+
+        if decoded_text.is_none() {
+            debug!("Failed to decode punycode because PunycodeDecoder::decode_punycode_labels_no_error_handling returned None");
+            return results;
+        }
+
+        let decoded_text = decoded_text.unwrap();
+        if !check_string_success(&decoded_text, text) {
+            info!(
+                "Failed to decode punycode because check_string_success returned false on string {}",
+                decoded_text
+            );
+            return results;
+        }
+
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+
+        results.update_checker(&checker_result);
+
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// helper function
+/// Finds every label (a run of letters, digits and hyphens) starting with `xn--`
+/// and replaces it with its decoded form. Everything else, such as other labels, the
+/// scheme or the path of a URL, is left untouched.
+/// Returns None if there are no `xn--` labels or one of them is not valid punycode.
+fn decode_punycode_labels_no_error_handling(text: &str) -> Option<String> {
+    let mut output = String::with_capacity(text.len());
+    let mut found_label = false;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let label_length = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .unwrap_or(rest.len());
+        if label_length == 0 {
+            // Not part of a label, copy the character over as it is
+            let c = rest.chars().next()?;
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let label = &rest[..label_length];
+        if label.len() > 4 && label[..4].eq_ignore_ascii_case("xn--") {
+            output.push_str(&decode_punycode(&label[4..])?);
+            found_label = true;
+        } else {
+            output.push_str(label);
+        }
+        rest = &rest[label_length..];
+    }
+
+    if !found_label {
+        return None;
+    }
+    Some(output)
+}
+
+/// Turns every label containing non-ASCII characters back into its `xn--` form.
+/// The LemmeKnow regexes only understand ASCII hostnames, so this lets the
+/// checkers recognise the decoded Unicode URL or domain.
+/// Returns None if there was nothing to encode.
+pub(crate) fn encode_punycode_labels(text: &str) -> Option<String> {
+    if text.is_ascii() {
+        return None;
+    }
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let label_length = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '-'))
+            .unwrap_or(rest.len());
+        if label_length == 0 {
+            let c = rest.chars().next()?;
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let label = &rest[..label_length];
+        if label.is_ascii() {
+            output.push_str(label);
+        } else {
+            output.push_str("xn--");
+            output.push_str(&encode_punycode(&label.to_lowercase())?);
+        }
+        rest = &rest[label_length..];
+    }
+    Some(output)
+}
+
+/// Punycode parameters from RFC 3492 section 5
+const BASE: u32 = 36;
+/// Minimum threshold for a digit
+const T_MIN: u32 = 1;
+/// Maximum threshold for a digit
+const T_MAX: u32 = 26;
+/// Bias adaptation skew
+const SKEW: u32 = 38;
+/// Bias adaptation damping for the first code point
+const DAMP: u32 = 700;
+/// The bias we start decoding with
+const INITIAL_BIAS: u32 = 72;
+/// The first non-ASCII code point
+const INITIAL_N: u32 = 128;
+
+/// Decodes a single punycode string (without the `xn--` prefix)
+/// following the decoding procedure in RFC 3492 section 6.2
+fn decode_punycode(input: &str) -> Option<String> {
+    // Everything before the last hyphen is copied as-is
+    let (basic, extended) = match input.rfind('-') {
+        Some(position) => (&input[..position], &input[position + 1..]),
+        None => ("", input),
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+    let mut output: Vec<char> = basic.chars().collect();
+
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut digits = extended.chars().peekable();
+
+    while digits.peek().is_some() {
+        let old_i = i;
+        let mut weight: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = decode_digit(digits.next()?)?;
+            i = i.checked_add(digit.checked_mul(weight)?)?;
+            let threshold = if k <= bias {
+                T_MIN
+            } else if k >= bias + T_MAX {
+                T_MAX
+            } else {
+                k - bias
+            };
+            if digit < threshold {
+                break;
+            }
+            weight = weight.checked_mul(BASE - threshold)?;
+            k += BASE;
+        }
+
+        let length = output.len() as u32 + 1;
+        bias = adapt(i - old_i, length, old_i == 0);
+        n = n.checked_add(i / length)?;
+        i %= length;
+        output.insert(i as usize, char::from_u32(n)?);
+        i += 1;
+    }
+
+    Some(output.into_iter().collect())
+}
+
+/// Encodes a single label as punycode (without the `xn--` prefix)
+/// following the encoding procedure in RFC 3492 section 6.3
+fn encode_punycode(input: &str) -> Option<String> {
+    let code_points: Vec<u32> = input.chars().map(u32::from).collect();
+    let mut output: String = input.chars().filter(char::is_ascii).collect();
+    let basic_length = output.len() as u32;
+    let mut handled = basic_length;
+    if basic_length > 0 {
+        output.push('-');
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+
+    while (handled as usize) < code_points.len() {
+        let next = *code_points.iter().filter(|c| **c >= n).min()?;
+        delta = delta.checked_add((next - n).checked_mul(handled + 1)?)?;
+        n = next;
+        for code_point in &code_points {
+            if *code_point < n {
+                delta = delta.checked_add(1)?;
+            }
+            if *code_point == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let threshold = if k <= bias {
+                        T_MIN
+                    } else if k >= bias + T_MAX {
+                        T_MAX
+                    } else {
+                        k - bias
+                    };
+                    if q < threshold {
+                        break;
+                    }
+                    output.push(encode_digit(
+                        threshold + (q - threshold) % (BASE - threshold),
+                    ));
+                    q = (q - threshold) / (BASE - threshold);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic_length);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta += 1;
+        n += 1;
+    }
+
+    Some(output)
+}
+
+/// Turns a digit value into its punycode character, the inverse of `decode_digit`
+fn encode_digit(digit: u32) -> char {
+    if digit < 26 {
+        char::from(b'a' + digit as u8)
+    } else {
+        char::from(b'0' + (digit - 26) as u8)
+    }
+}
+
+/// Turns a punycode character into its digit value
+/// `a-z` (or `A-Z`) are 0 to 25 and `0-9` are 26 to 35
+fn decode_digit(c: char) -> Option<u32> {
+    match c {
+        'a'..='z' => Some(c as u32 - 'a' as u32),
+        'A'..='Z' => Some(c as u32 - 'A' as u32),
+        '0'..='9' => Some(c as u32 - '0' as u32 + 26),
+        _ => None,
+    }
+}
+
+/// The bias adaptation function from RFC 3492 section 6.1
+fn adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_punycode_labels, PunycodeDecoder};
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    #[test]
+    fn punycode_decodes_hostname() {
+        // This tests if punycode can decode a single label hostname
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder.crack("xn--mnchen-3ya.de", &get_athena_checker());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "münchen.de");
+    }
+
+    #[test]
+    fn punycode_decodes_every_label() {
+        // Each xn-- label is decoded, plain labels are kept
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder.crack("xn--kxae4bafwg.xn--pxaix.gr", &get_athena_checker());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "ουτοπία.δπθ.gr");
+    }
+
+    #[test]
+    fn punycode_decodes_labels_without_basic_code_points() {
+        // Labels which are entirely non-ASCII have no `-` delimiter
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder.crack(
+            "https://xn--r8jz45g.xn--zckzah/index.html",
+            &get_athena_checker(),
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://例え.テスト/index.html"
+        );
+    }
+
+    #[test]
+    fn punycode_handles_invalid_label() {
+        // The label ends in the middle of a variable length integer
+        // It should return None
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder
+            .crack("xn--mnchen-3y.de", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn punycode_handles_hostname_without_xn_labels() {
+        // Nothing to decode in a plain ASCII hostname
+        // It should return None
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder
+            .crack("https://www.google.com", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn punycode_handles_panics() {
+        // This tests if punycode can handle panics
+        // It should return None
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn punycode_handles_panic_if_empty_string() {
+        // This tests if punycode can handle an empty string
+        // It should return None
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn punycode_handles_panic_if_emoji() {
+        // This tests if punycode can handle an emoji
+        // It should return None
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn punycode_encodes_unicode_labels() {
+        // Encoding is the inverse of decoding, ASCII labels are kept
+        assert_eq!(
+            encode_punycode_labels("https://www.bücher.example/path?q=1").unwrap(),
+            "https://www.xn--bcher-kva.example/path?q=1"
+        );
+        assert_eq!(
+            encode_punycode_labels("例え.テスト").unwrap(),
+            "xn--r8jz45g.xn--zckzah"
+        );
+    }
+
+    #[test]
+    fn punycode_encode_handles_ascii() {
+        // There is nothing to encode in plain ASCII
+        assert!(encode_punycode_labels("https://www.google.com").is_none());
+    }
+
+    #[test]
+    fn punycode_decoded_url_is_identified() {
+        // The decoded Unicode URL should still be recognised as a URL
+        let decoder = Decoder::<PunycodeDecoder>::new();
+        let result = decoder.crack("https://www.xn--mnchen-3ya.de", &get_athena_checker());
+        assert!(result.success);
+    }
+}
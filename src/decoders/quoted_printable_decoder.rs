@@ -0,0 +1,214 @@
+//! Decode a quoted-printable string
+//! Performs error handling and returns a string
+//! Call quoted_printable_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::CheckerTypes;
+use crate::decoders::interface::check_string_success;
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{debug, info, trace};
+use quoted_printable::ParseMode;
+
+/// The quoted-printable decoder, call:
+/// `let quoted_printable_decoder = Decoder::<QuotedPrintableDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = quoted_printable_decoder.crack(input)` to decode a quoted-printable string
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::quoted_printable_decoder::{QuotedPrintableDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_quoted_printable = Decoder::<QuotedPrintableDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// let result = decode_quoted_printable.crack("https://www.google.com/?q=3Dhello", &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "https://www.google.com/?q=hello");
+/// ```
+pub struct QuotedPrintableDecoder;
+
+impl Crack for Decoder<QuotedPrintableDecoder> {
+    fn new() -> Decoder<QuotedPrintableDecoder> {
+        Decoder {
+            name: "Quoted-printable",
+            description: "Quoted-Printable, or QP encoding, is a binary-to-text encoding system using printable ASCII characters (alphanumeric and the equals sign =) to transmit 8-bit data over a 7-bit data path or, generally, over a medium which is not 8-bit clean. It is defined as a MIME content transfer encoding for use in e-mail.",
+            link: "https://en.wikipedia.org/wiki/Quoted-printable",
+            tags: vec!["quoted_printable", "email", "mime", "decoder"],
+            popularity: 0.5,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying quoted-printable with text {:?}", text);
+        let decoded_text = decode_quoted_printable_no_error_handling(text);
+        let mut results = CrackResult::new(self, text.to_string());
+
+        if decoded_text.is_none() {
+            debug!("Failed to decode quoted-printable because QuotedPrintableDecoder::decode_quoted_printable_no_error_handling returned None");
+            return results;
+        }
+
+        let decoded_text = decoded_text.unwrap();
+        if !check_string_success(&decoded_text, text) {
+            info!(
+                "Failed to decode quoted-printable because check_string_success returned false on string {}",
+                decoded_text
+            );
+            return results;
+        }
+
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+
+        results.update_checker(&checker_result);
+
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// helper function
+fn decode_quoted_printable_no_error_handling(text: &str) -> Option<String> {
+    // Robust mode lets stray `=` signs through untouched, which would make any
+    // text containing an equals sign "decode". So we only try when there is at least
+    // one real escape (`=XX`) or soft line break (`=\n`) in the text.
+    if !contains_quoted_printable_escape(text) {
+        return None;
+    }
+    quoted_printable::decode(text, ParseMode::Robust)
+        .ok()
+        .map(|inner| String::from_utf8(inner).ok())?
+}
+
+/// Returns true if the text contains a hex escape like `=3D` or a soft line break
+fn contains_quoted_printable_escape(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.iter().enumerate().any(|(i, byte)| {
+        if *byte != b'=' {
+            return false;
+        }
+        match (bytes.get(i + 1), bytes.get(i + 2)) {
+            (Some(b'\n'), _) | (Some(b'\r'), Some(b'\n')) => true,
+            (Some(first), Some(second)) => first.is_ascii_hexdigit() && second.is_ascii_hexdigit(),
+            _ => false,
+        }
+    })
+}
+
+/// Encodes the text as quoted-printable, escaping bytes that aren't printable ASCII
+pub(crate) fn encode_quoted_printable(text: &str) -> String {
+    quoted_printable::encode_to_str(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_quoted_printable_no_error_handling, encode_quoted_printable, QuotedPrintableDecoder,
+    };
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    #[test]
+    fn quoted_printable_decodes_successfully() {
+        // This tests if quoted-printable can decode quoted-printable successfully
+        let decoder = Decoder::<QuotedPrintableDecoder>::new();
+        let result = decoder.crack(
+            "J'interdis aux marchands de vanter trop leurs marchandises. Car ils se font vite p=C3=A9dagogues et t'enseignent comme but ce qui n'est par essence qu'un moyen.",
+            &get_athena_checker(),
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "J'interdis aux marchands de vanter trop leurs marchandises. Car ils se font vite pédagogues et t'enseignent comme but ce qui n'est par essence qu'un moyen."
+        );
+    }
+
+    #[test]
+    fn quoted_printable_decodes_soft_line_breaks() {
+        // Soft line breaks (`=` at the end of a line) join the lines back together
+        let decoder = Decoder::<QuotedPrintableDecoder>::new();
+        let result = decoder.crack("hello =\nthere =3D general", &get_athena_checker());
+        assert_eq!(result.unencrypted_text.unwrap()[0], "hello there = general");
+    }
+
+    #[test]
+    fn quoted_printable_handles_text_without_escapes() {
+        // Text with a stray equals sign is not quoted-printable
+        // It should return None
+        let decoder = Decoder::<QuotedPrintableDecoder>::new();
+        let result = decoder
+            .crack("x = y + z", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn quoted_printable_handles_panics() {
+        // This tests if quoted-printable can handle panics
+        // It should return None
+        let decoder = Decoder::<QuotedPrintableDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn quoted_printable_handles_panic_if_empty_string() {
+        // This tests if quoted-printable can handle an empty string
+        // It should return None
+        let decoder = Decoder::<QuotedPrintableDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn quoted_printable_handles_panic_if_emoji() {
+        // This tests if quoted-printable can handle an emoji
+        // It should return None
+        let decoder = Decoder::<QuotedPrintableDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_quoted_printable("héllo = world");
+        assert_eq!(encoded, "h=C3=A9llo =3D world");
+        assert_eq!(
+            decode_quoted_printable_no_error_handling(&encoded).unwrap(),
+            "héllo = world"
+        );
+    }
+}
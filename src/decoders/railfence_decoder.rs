@@ -35,6 +35,7 @@ impl Crack for Decoder<RailfenceDecoder> {
             for offset in 0..=(rails * 2 - 3) {
                 let decoded_text = railfence_decoder(text, rails, offset);
                 decoded_strings.push(decoded_text);
+                results.candidate_keys.push(format!("{rails}:{offset}"));
                 let borrowed_decoded_text = &decoded_strings[decoded_strings.len() - 1];
                 if !check_string_success(borrowed_decoded_text, text) {
                     info!(
@@ -51,6 +52,8 @@ impl Crack for Decoder<RailfenceDecoder> {
                         offset
                     );
                     results.unencrypted_text = Some(vec![borrowed_decoded_text.to_string()]);
+                    results.key = Some(format!("{rails}:{offset}"));
+                    results.candidate_keys.clear();
                     results.update_checker(&checker_result);
                     return results;
                 }
@@ -59,6 +62,17 @@ impl Crack for Decoder<RailfenceDecoder> {
         results.unencrypted_text = Some(decoded_strings);
         results
     }
+    /// Decodes with a key of `rails` or `rails:offset`, like `3:1`
+    fn crack_with_key(&self, text: &str, key: &str, checker: &CheckerTypes) -> Option<CrackResult> {
+        let (rails, offset) = parse_railfence_key(key).ok()?;
+        let mut results = CrackResult::new(self, text.to_string());
+        let decoded_text = railfence_decoder(text, rails, offset);
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+        results.key = Some(format!("{rails}:{offset}"));
+        results.update_checker(&checker_result);
+        Some(results)
+    }
     /// Gets all tags for this decoder
     fn get_tags(&self) -> &Vec<&str> {
         &self.tags
@@ -87,9 +101,41 @@ fn zigzag(n: usize, offset: usize) -> impl Iterator<Item = usize> {
     (0..n - 1).chain((1..n).rev()).cycle().skip(offset)
 }
 
+/// Reads a key of `rails` or `rails:offset`, the offset is 0 if it's not given
+pub(crate) fn parse_railfence_key(key: &str) -> Result<(usize, usize), String> {
+    let (rails, offset) = key.split_once(':').unwrap_or((key, "0"));
+    let rails: usize = rails
+        .trim()
+        .parse()
+        .map_err(|_| format!("the rails {rails:?} aren't a number"))?;
+    let offset: usize = offset
+        .trim()
+        .parse()
+        .map_err(|_| format!("the offset {offset:?} isn't a number"))?;
+    if rails < 2 {
+        return Err("railfence needs at least 2 rails".to_string());
+    }
+    if offset > rails * 2 - 3 {
+        return Err(format!(
+            "the offset can be at most {} with {rails} rails",
+            rails * 2 - 3
+        ));
+    }
+    Ok((rails, offset))
+}
+
+/// Encodes the text by writing it in a zigzag over the rails and reading off each rail.
+/// The offset is how far into the zigzag we start, like the decoder tries.
+pub(crate) fn railfence_encoder(text: &str, rails: usize, offset: usize) -> String {
+    let mut chars: Vec<(usize, char)> = zigzag(rails, offset).zip(text.chars()).collect();
+    // The sort is stable so each rail keeps its characters in order
+    chars.sort_by_key(|(rail, _)| *rail);
+    chars.into_iter().map(|(_, c)| c).collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::RailfenceDecoder;
+    use super::{railfence_decoder, railfence_encoder, RailfenceDecoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -141,4 +187,13 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = railfence_encoder("WEAREDISCOVERED", 3, 0);
+        assert_eq!(encoded, "WECRERDSOEEAIVD");
+        assert_eq!(railfence_decoder(&encoded, 3, 0), "WEAREDISCOVERED");
+        let encoded = railfence_encoder("hello world", 4, 2);
+        assert_eq!(railfence_decoder(&encoded, 4, 2), "hello world");
+    }
 }
@@ -67,6 +67,11 @@ impl Crack for Decoder<ReverseDecoder> {
     }
 }
 
+/// Reverses the text, which is its own inverse
+pub(crate) fn encode_reverse(text: &str) -> String {
+    text.chars().rev().collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +107,10 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        assert_eq!(encode_reverse("héllo"), "olléh");
+        assert_eq!(encode_reverse(&encode_reverse("héllo")), "héllo");
+    }
 }
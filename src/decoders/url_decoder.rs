@@ -95,9 +95,14 @@ fn decode_url_no_error_handling(text: &str) -> Option<String> {
     None
 }
 
+/// Percent-encodes everything in the text except letters, digits and `-._~`
+pub(crate) fn encode_url(text: &str) -> String {
+    urlencoding::encode(text).into_owned()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::URLDecoder;
+    use super::{decode_url_no_error_handling, encode_url, URLDecoder};
     use crate::{
         checkers::{
             athena::Athena,
@@ -162,4 +167,14 @@ mod tests {
             .unencrypted_text;
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_url("hello world?&é");
+        assert_eq!(encoded, "hello%20world%3F%26%C3%A9");
+        assert_eq!(
+            decode_url_no_error_handling(&encoded).unwrap(),
+            "hello world?&é"
+        );
+    }
 }
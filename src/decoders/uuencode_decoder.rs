@@ -0,0 +1,320 @@
+//! Decode a uuencoded string
+//! Performs error handling and returns a string
+//! Call uuencode_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::file_type_checker::text_to_bytes;
+use crate::checkers::CheckerTypes;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{debug, info, trace};
+
+/// The uuencode decoder, call:
+/// `let uuencode_decoder = Decoder::<UUEncodeDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = uuencode_decoder.crack(input)` to decode a uuencoded string
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::uuencode_decoder::{UUEncodeDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_uuencode = Decoder::<UUEncodeDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// let result = decode_uuencode.crack("begin 644 hello.txt\n+:&5L;&\\@=V]R;&0`\n`\nend", &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "hello world");
+/// ```
+pub struct UUEncodeDecoder;
+
+impl Crack for Decoder<UUEncodeDecoder> {
+    fn new() -> Decoder<UUEncodeDecoder> {
+        Decoder {
+            name: "UUencode",
+            description: "uuencoding is a form of binary-to-text encoding that originated in the Unix programs uuencode and uudecode for encoding binary data for transmission in email systems. Each line starts with a character giving the number of bytes it encodes, followed by groups of four printable characters for every three bytes.",
+            link: "https://en.wikipedia.org/wiki/Uuencoding",
+            tags: vec!["uuencode", "email", "usenet", "decoder", "base"],
+            popularity: 0.3,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying uuencode with text {:?}", text);
+        let decoded_bytes = decode_uuencode_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().and_then(bytes_to_string);
+        let mut results = CrackResult::new(self, text.to_string());
+
+        if decoded_text.is_none() {
+            debug!("Failed to decode uuencode because UUEncodeDecoder::decode_uuencode_no_error_handling returned None");
+            return results;
+        }
+
+        let decoded_text = decoded_text.unwrap();
+        if !check_string_success(&decoded_text, text) {
+            info!(
+                "Failed to decode uuencode because check_string_success returned false on string {}",
+                decoded_text
+            );
+            return results;
+        }
+
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
+
+        results.update_checker(&checker_result);
+
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// helper function
+fn decode_uuencode_no_error_handling(text: &str) -> Option<Vec<u8>> {
+    decode_uu_style_lines(text, uuencode_char_value)
+}
+
+/// uuencode maps `value + 32` to a character, with a backtick commonly used for 0
+fn uuencode_char_value(c: char) -> Option<u8> {
+    match c {
+        '`' => Some(0),
+        ' '..='_' => Some(c as u8 - b' '),
+        _ => None,
+    }
+}
+
+/// Decodes the line based format shared by uuencode and xxencode.
+/// `char_value` maps a character of the alphabet to its 6 bit value.
+///
+/// Each line starts with a length character saying how many bytes the line holds
+/// (at most 45), followed by 4 characters for every 3 bytes. The length is checked against
+/// the line so random text does not decode into garbage.
+/// The `begin <mode> <name>` / `end` framing is optional.
+pub(crate) fn decode_uu_style_lines(
+    text: &str,
+    char_value: fn(char) -> Option<u8>,
+) -> Option<Vec<u8>> {
+    /// The most bytes a single line is allowed to encode
+    const MAX_BYTES_PER_LINE: usize = 45;
+
+    let mut lines = text
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .peekable();
+    let framed = lines
+        .peek()
+        .is_some_and(|first| first.starts_with("begin "));
+    if framed {
+        lines.next();
+    }
+
+    let mut decoded: Vec<u8> = Vec::new();
+    let mut terminated = false;
+    for line in lines {
+        if line == "end" {
+            break;
+        }
+        if terminated {
+            // Only `end` is allowed after the zero length line
+            if line.is_empty() {
+                continue;
+            }
+            return None;
+        }
+        let mut chars = line.chars();
+        let length_char = match chars.next() {
+            Some(c) => c,
+            // Blank lines between data lines are not uuencode
+            None if framed => return None,
+            None => continue,
+        };
+        let byte_count = usize::from(char_value(length_char)?);
+        if byte_count == 0 {
+            terminated = true;
+            continue;
+        }
+        if byte_count > MAX_BYTES_PER_LINE {
+            return None;
+        }
+
+        let body: Vec<u8> = chars.map(char_value).collect::<Option<Vec<u8>>>()?;
+        // Encoders pad to a full group of 4, but some strip trailing padding characters
+        let min_length = (byte_count * 4).div_ceil(3);
+        let max_length = byte_count.div_ceil(3) * 4;
+        if body.len() < min_length || body.len() > max_length {
+            return None;
+        }
+
+        let mut line_bytes: Vec<u8> = Vec::with_capacity(max_length / 4 * 3);
+        for group in body.chunks(4) {
+            let mut values = [0u8; 4];
+            values[..group.len()].copy_from_slice(group);
+            line_bytes.push((values[0] << 2) | (values[1] >> 4));
+            line_bytes.push((values[1] << 4) | (values[2] >> 2));
+            line_bytes.push((values[2] << 6) | values[3]);
+        }
+        line_bytes.truncate(byte_count);
+        decoded.extend(line_bytes);
+    }
+
+    if decoded.is_empty() {
+        return None;
+    }
+    Some(decoded)
+}
+
+/// Encodes the text with uuencode, framed by `begin 644 data` and `end` lines
+pub(crate) fn encode_uuencode(text: &str) -> String {
+    encode_uu_style_lines(&text_to_bytes(text), uuencode_value_char)
+}
+
+/// The character uuencode uses for a 6 bit value, zero is a backtick rather than a space
+fn uuencode_value_char(value: u8) -> char {
+    if value == 0 {
+        '`'
+    } else {
+        char::from(value + b' ')
+    }
+}
+
+/// Encodes bytes as lines of up to 45 bytes, each line starting with how many bytes it has.
+/// Shared with xxencode, which only differs in the characters it uses.
+pub(crate) fn encode_uu_style_lines(bytes: &[u8], value_char: fn(u8) -> char) -> String {
+    /// The most bytes a single line encodes
+    const BYTES_PER_LINE: usize = 45;
+
+    let mut encoded = String::from("begin 644 data\n");
+    for line in bytes.chunks(BYTES_PER_LINE) {
+        encoded.push(value_char(line.len() as u8));
+        for group in line.chunks(3) {
+            let mut values = [0u8; 3];
+            values[..group.len()].copy_from_slice(group);
+            encoded.push(value_char(values[0] >> 2));
+            encoded.push(value_char(((values[0] & 0x03) << 4) | (values[1] >> 4)));
+            encoded.push(value_char(((values[1] & 0x0F) << 2) | (values[2] >> 6)));
+            encoded.push(value_char(values[2] & 0x3F));
+        }
+        encoded.push('\n');
+    }
+    // A zero length line ends the data
+    encoded.push(value_char(0));
+    encoded.push_str("\nend");
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_uuencode_no_error_handling, encode_uuencode, UUEncodeDecoder};
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    #[test]
+    fn uuencode_decodes_successfully_without_framing() {
+        // This tests if uuencode can decode a bare uuencoded line
+        let decoder = Decoder::<UUEncodeDecoder>::new();
+        let result = decoder.crack("6:'1T<',Z+R]W=W<N9V]O9VQE+F-O;0  ", &get_athena_checker());
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://www.google.com"
+        );
+    }
+
+    #[test]
+    fn uuencode_decodes_successfully_with_framing() {
+        // This tests if uuencode can decode multiple lines inside begin / end
+        let decoder = Decoder::<UUEncodeDecoder>::new();
+        let result = decoder.crack(
+            "begin 644 fox.txt\nM5&AE('%U:6-K(&)R;W=N(&9O>\"!J=6UP<R!O=F5R('1H92!L87IY(&1O9RX@\nH4&%C:R!M>2!B;W@@=VET:\"!F:79E(&1O>F5N(&QI<75O<B!J=6=S+@``\n`\nend\n",
+            &get_athena_checker(),
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "The quick brown fox jumps over the lazy dog. Pack my box with five dozen liquor jugs."
+        );
+    }
+
+    #[test]
+    fn uuencode_handles_wrong_line_length() {
+        // The length character says 22 bytes but the line is too short for that
+        // It should return None
+        let decoder = Decoder::<UUEncodeDecoder>::new();
+        let result = decoder
+            .crack("6:'1T<',Z+R]W=W<N9V", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn uuencode_handles_panics() {
+        // This tests if uuencode can handle panics
+        // It should return None
+        let decoder = Decoder::<UUEncodeDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn uuencode_handles_panic_if_empty_string() {
+        // This tests if uuencode can handle an empty string
+        // It should return None
+        let decoder = Decoder::<UUEncodeDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn uuencode_handles_panic_if_emoji() {
+        // This tests if uuencode can handle an emoji
+        // It should return None
+        let decoder = Decoder::<UUEncodeDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_uuencode("Cat");
+        assert_eq!(encoded, "begin 644 data\n#0V%T\n`\nend");
+        let long_text = "The quick brown fox jumps over the lazy dog. ".repeat(3);
+        let encoded = encode_uuencode(&long_text);
+        assert_eq!(encoded.lines().count(), 6);
+        assert_eq!(
+            decode_uuencode_no_error_handling(&encoded).unwrap(),
+            long_text.as_bytes()
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Extract a message hidden in trailing whitespace
+//! Performs error handling and returns a string
+//! Call whitespace_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::CheckerTypes;
+
+use super::binary_decoder::binary_to_string;
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use super::zero_width_decoder::is_printable;
+
+use log::{debug, info, trace};
+
+/// The whitespace decoder, call:
+/// `let whitespace_decoder = Decoder::<WhitespaceDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = whitespace_decoder.crack(input)` to extract a message hidden in trailing whitespace
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::whitespace_decoder::{WhitespaceDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_whitespace = Decoder::<WhitespaceDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// // "hi" is 01101000 01101001, with a space as 0 and a tab as 1
+/// let result = decode_whitespace.crack("Nothing to see \t\t \t   \n here \t\t \t  \t", &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "hi");
+/// ```
+pub struct WhitespaceDecoder;
+
+impl Crack for Decoder<WhitespaceDecoder> {
+    fn new() -> Decoder<WhitespaceDecoder> {
+        Decoder {
+            name: "Whitespace steganography",
+            description: "Spaces and tabs at the end of a line are invisible in most editors, so tools like SNOW hide a binary message in them, usually with a space as 0 and a tab as 1.",
+            link: "https://darkside.com.au/snow/",
+            tags: vec!["whitespace", "steganography", "snow", "decoder"],
+            popularity: 0.3,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying whitespace steganography with text {:?}", text);
+        let mut results = CrackResult::new(self, text.to_string());
+        let decoded_strings = decode_whitespace_no_error_handling(text);
+
+        if decoded_strings.is_none() {
+            debug!("Failed to decode whitespace steganography because WhitespaceDecoder::decode_whitespace_no_error_handling returned None");
+            return results;
+        }
+
+        // We don't know which of space and tab is 1, so both readings are candidates
+        let decoded_strings = decoded_strings.unwrap();
+        for decoded_text in decoded_strings.iter() {
+            let checker_result = checker.check(decoded_text);
+            if checker_result.is_identified {
+                info!("Found a whitespace message {}", decoded_text);
+                results.unencrypted_text = Some(vec![decoded_text.to_string()]);
+                results.update_checker(&checker_result);
+                return results;
+            }
+        }
+        results.unencrypted_text = Some(decoded_strings);
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// Returns true if the trailing whitespace of the text decodes to a message.
+/// Used so we don't stop at the innocent looking cover text.
+pub(crate) fn contains_whitespace_message(text: &str) -> bool {
+    decode_whitespace_no_error_handling(text).is_some()
+}
+
+/// helper function
+/// Collects the run of spaces and tabs at the end of every line and reads it as bits.
+/// Normal text has the odd trailing space, but almost never a tab, so we need at least one.
+fn decode_whitespace_no_error_handling(text: &str) -> Option<Vec<String>> {
+    let trailing: String = text
+        .lines()
+        .map(|line| {
+            let line = line.trim_end_matches('\r');
+            let content = line.trim_end_matches([' ', '\t']);
+            &line[content.len()..]
+        })
+        .collect();
+    if trailing.len() < 8 || !trailing.contains('\t') {
+        return None;
+    }
+    let whole_bytes = trailing.len() - trailing.len() % 8;
+    let trailing = &trailing[..whole_bytes];
+
+    let mut candidates: Vec<String> = [(' ', '\t'), ('\t', ' ')]
+        .iter()
+        .map(|(zero, one)| {
+            let bits: String = trailing
+                .chars()
+                .map(|c| {
+                    if c == *one {
+                        '1'
+                    } else if c == *zero {
+                        '0'
+                    } else {
+                        c
+                    }
+                })
+                .collect();
+            binary_to_string(&bits, 8)
+        })
+        .filter(|candidate| is_printable(candidate))
+        .collect();
+    candidates.dedup();
+    if candidates.is_empty() {
+        return None;
+    }
+    Some(candidates)
+}
+
+/// Hides the text in trailing whitespace after the cover text, a space for each 0 bit
+/// and a tab for each 1 bit. Any trailing whitespace already in the cover is removed
+/// so it doesn't become part of the message.
+pub(crate) fn encode_whitespace(text: &str, cover: &str) -> String {
+    let cover: Vec<&str> = cover
+        .lines()
+        .map(|line| line.trim_end_matches([' ', '\t', '\r']))
+        .collect();
+    let bits: String = text
+        .bytes()
+        .map(|byte| format!("{byte:08b}"))
+        .collect::<String>()
+        .replace('0', " ")
+        .replace('1', "\t");
+    format!("{}{bits}", cover.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        contains_whitespace_message, decode_whitespace_no_error_handling, encode_whitespace,
+        WhitespaceDecoder,
+    };
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    // Hides the message in the trailing whitespace of the cover lines, one byte per line
+    fn hide(message: &str, zero: char, one: char) -> String {
+        message
+            .bytes()
+            .map(|byte| {
+                let bits: String = (0..8)
+                    .rev()
+                    .map(|i| if (byte >> i) & 1 == 1 { one } else { zero })
+                    .collect();
+                format!("cover line{bits}\n")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn whitespace_decodes_successfully() {
+        // The hidden URL is spread over the ends of many lines
+        let decoder = Decoder::<WhitespaceDecoder>::new();
+        let text = hide("https://www.google.com", ' ', '\t');
+        let result = decoder.crack(&text, &get_athena_checker());
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://www.google.com"
+        );
+    }
+
+    #[test]
+    fn whitespace_decodes_with_tab_as_zero() {
+        // Some tools use a tab for 0 instead
+        let decoder = Decoder::<WhitespaceDecoder>::new();
+        let text = hide("https://www.google.com", '\t', ' ');
+        let result = decoder.crack(&text, &get_athena_checker());
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://www.google.com"
+        );
+    }
+
+    #[test]
+    fn whitespace_detects_hidden_message() {
+        assert!(contains_whitespace_message(&hide("secret", ' ', '\t')));
+        assert!(!contains_whitespace_message(
+            "cover line \nanother line  \n"
+        ));
+    }
+
+    #[test]
+    fn whitespace_handles_trailing_spaces_without_tabs() {
+        // Plenty of normal text has trailing spaces
+        // It should return None
+        let decoder = Decoder::<WhitespaceDecoder>::new();
+        let result = decoder
+            .crack("hello        \nworld        \n", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn whitespace_handles_panics() {
+        // This tests if whitespace can handle panics
+        // It should return None
+        let decoder = Decoder::<WhitespaceDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn whitespace_handles_panic_if_empty_string() {
+        // This tests if whitespace can handle an empty string
+        // It should return None
+        let decoder = Decoder::<WhitespaceDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn whitespace_handles_panic_if_emoji() {
+        // This tests if whitespace can handle an emoji
+        // It should return None
+        let decoder = Decoder::<WhitespaceDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_whitespace("hidden", "Nothing to see here  \nMove along ");
+        assert!(encoded.starts_with("Nothing to see here\nMove along"));
+        assert_eq!(
+            decode_whitespace_no_error_handling(&encoded).unwrap()[0],
+            "hidden"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Decode an xxencoded string
+//! Performs error handling and returns a string
+//! Call xxencode_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::file_type_checker::text_to_bytes;
+use crate::checkers::CheckerTypes;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+use super::uuencode_decoder::{decode_uu_style_lines, encode_uu_style_lines};
+
+use log::{debug, info, trace};
+
+/// The xxencode decoder, call:
+/// `let xxencode_decoder = Decoder::<XXEncodeDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = xxencode_decoder.crack(input)` to decode an xxencoded string
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::xxencode_decoder::{XXEncodeDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_xxencode = Decoder::<XXEncodeDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// let result = decode_xxencode.crack("begin 644 hello.txt\n9O4JgP4wURqxmP4E+\n+\nend", &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "hello world");
+/// ```
+pub struct XXEncodeDecoder;
+
+impl Crack for Decoder<XXEncodeDecoder> {
+    fn new() -> Decoder<XXEncodeDecoder> {
+        Decoder {
+            name: "XXencode",
+            description: "xxencoding is a binary-to-text encoding similar to uuencoding, but using only alphanumeric characters plus '+' and '-'. It was designed to survive translation between ASCII and EBCDIC on gateways that mangled uuencode's punctuation.",
+            link: "https://en.wikipedia.org/wiki/Xxencoding",
+            tags: vec!["xxencode", "uuencode", "email", "usenet", "decoder", "base"],
+            popularity: 0.2,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying xxencode with text {:?}", text);
+        let decoded_bytes = decode_xxencode_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().and_then(bytes_to_string);
+        let mut results = CrackResult::new(self, text.to_string());
+
+        if decoded_text.is_none() {
+            debug!("Failed to decode xxencode because XXEncodeDecoder::decode_xxencode_no_error_handling returned None");
+            return results;
+        }
+
+        let decoded_text = decoded_text.unwrap();
+        if !check_string_success(&decoded_text, text) {
+            info!(
+                "Failed to decode xxencode because check_string_success returned false on string {}",
+                decoded_text
+            );
+            return results;
+        }
+
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
+
+        results.update_checker(&checker_result);
+
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// helper function
+fn decode_xxencode_no_error_handling(text: &str) -> Option<Vec<u8>> {
+    decode_uu_style_lines(text, xxencode_char_value)
+}
+
+/// xxencode uses the alphabet `+-0-9A-Za-z`, so `+` is 0 and `z` is 63
+fn xxencode_char_value(c: char) -> Option<u8> {
+    match c {
+        '+' => Some(0),
+        '-' => Some(1),
+        '0'..='9' => Some(c as u8 - b'0' + 2),
+        'A'..='Z' => Some(c as u8 - b'A' + 12),
+        'a'..='z' => Some(c as u8 - b'a' + 38),
+        _ => None,
+    }
+}
+
+/// Encodes the text with xxencode, framed by `begin 644 data` and `end` lines
+pub(crate) fn encode_xxencode(text: &str) -> String {
+    encode_uu_style_lines(&text_to_bytes(text), xxencode_value_char)
+}
+
+/// The character xxencode uses for a 6 bit value, the reverse of `xxencode_char_value`
+fn xxencode_value_char(value: u8) -> char {
+    /// The xxencode alphabet, in order of value
+    const ALPHABET: &[u8; 64] = b"+-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    char::from(ALPHABET[usize::from(value & 0x3F)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_xxencode_no_error_handling, encode_xxencode, XXEncodeDecoder};
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    #[test]
+    fn xxencode_decodes_successfully_without_framing() {
+        // This tests if xxencode can decode a bare xxencoded line
+        let decoder = Decoder::<XXEncodeDecoder>::new();
+        let result = decoder.crack("KO5FoQ5Au9mxrRrQiNqxjNqlZ9aBjPE++", &get_athena_checker());
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://www.google.com"
+        );
+    }
+
+    #[test]
+    fn xxencode_decodes_successfully_with_framing() {
+        // This tests if xxencode can decode multiple lines inside begin / end
+        let decoder = Decoder::<XXEncodeDecoder>::new();
+        let result = decoder.crack(
+            "begin 644 fox.txt\nhJ4VZ653pOKBf647mPrRi64NjS0-eRKpkQm-jRaJm65FcNG-gMLdt64FjNmsU\ncI43XOm-hSG-WPrUURqZoO0-aOLNZ64FjSaJi64ldQLJjQW-eRKRn9U++\n+\nend\n",
+            &get_athena_checker(),
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "The quick brown fox jumps over the lazy dog. Pack my box with five dozen liquor jugs."
+        );
+    }
+
+    #[test]
+    fn xxencode_handles_wrong_line_length() {
+        // `h` says the line holds 45 bytes, but "ello" can't hold that many
+        // It should return None
+        let decoder = Decoder::<XXEncodeDecoder>::new();
+        let result = decoder
+            .crack("hello", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn xxencode_handles_panics() {
+        // This tests if xxencode can handle panics
+        // It should return None
+        let decoder = Decoder::<XXEncodeDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn xxencode_handles_panic_if_empty_string() {
+        // This tests if xxencode can handle an empty string
+        // It should return None
+        let decoder = Decoder::<XXEncodeDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn xxencode_handles_panic_if_emoji() {
+        // This tests if xxencode can handle an emoji
+        // It should return None
+        let decoder = Decoder::<XXEncodeDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_xxencode("Cat");
+        assert_eq!(encoded, "begin 644 data\n1Eq3o\n+\nend");
+        assert_eq!(decode_xxencode_no_error_handling(&encoded).unwrap(), b"Cat");
+    }
+}
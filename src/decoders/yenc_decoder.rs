@@ -0,0 +1,333 @@
+//! Decode a yEnc encoded string
+//! Performs error handling and returns a string
+//! Call yenc_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::file_type_checker::text_to_bytes;
+use crate::checkers::CheckerTypes;
+use crate::decoders::interface::{bytes_to_string, check_string_success};
+
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{debug, info, trace};
+
+/// The yEnc decoder, call:
+/// `let yenc_decoder = Decoder::<YEncDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = yenc_decoder.crack(input)` to decode a yEnc string
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::yenc_decoder::{YEncDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_yenc = Decoder::<YEncDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// let result = decode_yenc.crack("=ybegin line=128 size=11 name=hello.txt\n\u{92}\u{8f}\u{96}\u{96}\u{99}J\u{a1}\u{99}\u{9c}\u{96}\u{8e}\n=yend size=11", &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "hello world");
+/// ```
+pub struct YEncDecoder;
+
+impl Crack for Decoder<YEncDecoder> {
+    fn new() -> Decoder<YEncDecoder> {
+        Decoder {
+            name: "yEnc",
+            description: "yEnc is a binary-to-text encoding scheme for transferring binary files in messages on Usenet or via e-mail. It adds 42 to every byte and only escapes the few bytes that would break the message, so it has far less overhead than uuencode or base64.",
+            link: "https://en.wikipedia.org/wiki/YEnc",
+            tags: vec!["yenc", "usenet", "decoder"],
+            popularity: 0.2,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying yEnc with text {:?}", text);
+        let decoded_bytes = decode_yenc_no_error_handling(text);
+        let decoded_text = decoded_bytes.as_deref().and_then(bytes_to_string);
+        let mut results = CrackResult::new(self, text.to_string());
+
+        if decoded_text.is_none() {
+            debug!("Failed to decode yEnc because YEncDecoder::decode_yenc_no_error_handling returned None");
+            return results;
+        }
+
+        let decoded_text = decoded_text.unwrap();
+        if !check_string_success(&decoded_text, text) {
+            info!(
+                "Failed to decode yEnc because check_string_success returned false on string {}",
+                decoded_text
+            );
+            return results;
+        }
+
+        let checker_result = checker.check(&decoded_text);
+        results.unencrypted_text = Some(vec![decoded_text]);
+        results.unencrypted_bytes = decoded_bytes;
+
+        results.update_checker(&checker_result);
+
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// helper function
+/// yEnc shifts every byte, so any text at all would "decode" without the framing.
+/// We require the `=ybegin` header and `=yend` trailer and check the data against
+/// their `line=` and `size=` values.
+/// yEnc output is 8 bit, so we expect each byte as a char in U+0000..=U+00FF (Latin-1).
+fn decode_yenc_no_error_handling(text: &str) -> Option<Vec<u8>> {
+    let mut lines = text.lines().map(|line| line.trim_end_matches('\r'));
+    let header = lines.next()?.strip_prefix("=ybegin ")?;
+    let line_length: usize = header_value(header, "line")?.parse().ok()?;
+    let expected_size: usize = header_value(header, "size")?.parse().ok()?;
+    // Every byte takes at least one character, so a bigger size can't be right.
+    // Checking it first also stops a made up size from allocating all our memory.
+    if expected_size > text.len() {
+        return None;
+    }
+    // A line may go one over when it ends in an escape sequence
+    let max_line_length = line_length.checked_add(1)?;
+
+    let mut decoded: Vec<u8> = Vec::with_capacity(expected_size);
+    let mut trailer = None;
+    for line in lines {
+        if let Some(end) = line.strip_prefix("=yend") {
+            trailer = Some(end);
+            break;
+        }
+        if line.starts_with("=ypart ") {
+            continue;
+        }
+        if line.chars().count() > max_line_length {
+            return None;
+        }
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            let byte = if c == '=' {
+                latin1_byte(chars.next()?)?.wrapping_sub(64)
+            } else {
+                latin1_byte(c)?
+            };
+            decoded.push(byte.wrapping_sub(42));
+        }
+    }
+
+    let trailer_size: usize = header_value(trailer?, "size")?.parse().ok()?;
+    if decoded.len() != expected_size || trailer_size != expected_size {
+        return None;
+    }
+    Some(decoded)
+}
+
+/// Finds `key=value` in a yEnc header or trailer line
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let prefix = format!("{key}=");
+    let start = if header.starts_with(&prefix) {
+        0
+    } else {
+        header.find(&format!(" {prefix}"))? + 1
+    };
+    header[start + prefix.len()..].split(' ').next()
+}
+
+/// Turns a Latin-1 char back into the byte it came from
+fn latin1_byte(c: char) -> Option<u8> {
+    u8::try_from(u32::from(c)).ok()
+}
+
+/// Encodes the text with yEnc, with `=ybegin` and `=yend` lines around 128 character lines.
+/// Each byte is shifted by 42 and NUL, line feed, carriage return and `=` are escaped.
+pub(crate) fn encode_yenc(text: &str) -> String {
+    /// How long we let the lines get, 128 is what most encoders use
+    const LINE_LENGTH: usize = 128;
+
+    let bytes = text_to_bytes(text);
+    let mut encoded = format!(
+        "=ybegin line={LINE_LENGTH} size={} name=data\n",
+        bytes.len()
+    );
+    let mut line_length = 0;
+    for byte in &bytes {
+        let value = byte.wrapping_add(42);
+        if matches!(value, 0 | b'\n' | b'\r' | b'=') {
+            encoded.push('=');
+            encoded.push(char::from(value.wrapping_add(64)));
+            line_length += 2;
+        } else {
+            encoded.push(char::from(value));
+            line_length += 1;
+        }
+        if line_length >= LINE_LENGTH {
+            encoded.push('\n');
+            line_length = 0;
+        }
+    }
+    if line_length > 0 {
+        encoded.push('\n');
+    }
+    encoded.push_str(&format!("=yend size={}", bytes.len()));
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_yenc_no_error_handling, encode_yenc, YEncDecoder};
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    #[test]
+    fn yenc_decodes_successfully() {
+        // This tests if yEnc can decode yEnc successfully
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder.crack(
+            "=ybegin line=128 size=22 name=google url.txt\n\u{92}\u{9e}\u{9e}\u{9a}\u{9d}dYY\u{a1}\u{a1}\u{a1}X\u{91}\u{99}\u{99}\u{91}\u{96}\u{8f}X\u{8d}\u{99}\u{97}\n=yend size=22",
+            &get_athena_checker(),
+        );
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://www.google.com"
+        );
+    }
+
+    #[test]
+    fn yenc_decodes_escaped_characters() {
+        // `=` (19 + 42) is a critical character so it is escaped as `=}`
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder.crack(
+            "=ybegin line=128 size=3 name=a\n\u{8b}=}\u{8c}\n=yend size=3",
+            &get_athena_checker(),
+        );
+        assert_eq!(result.unencrypted_text.unwrap()[0], "a\u{13}b");
+    }
+
+    #[test]
+    fn yenc_handles_wrong_size() {
+        // The header says 12 bytes but there are only 11
+        // It should return None
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder
+            .crack(
+                "=ybegin line=128 size=12 name=hello.txt\n\u{92}\u{8f}\u{96}\u{96}\u{99}J\u{a1}\u{99}\u{9c}\u{96}\u{8e}\n=yend size=12",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn yenc_handles_oversized_header() {
+        // The header claims far more bytes than the text could hold,
+        // we shouldn't try to make room for them
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder
+            .crack(
+                "=ybegin line=128 size=99999999999999 name=a\n\u{8b}\u{8c}\n=yend size=99999999999999",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn yenc_handles_overflowing_line_length() {
+        // line=usize::MAX + 1 would overflow
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder
+            .crack(
+                "=ybegin line=18446744073709551615 size=2 name=a\n\u{8b}\u{8c}\n=yend size=2",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn yenc_handles_missing_framing() {
+        // Without =ybegin we can't tell yEnc apart from any other text
+        // It should return None
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder
+            .crack(
+                "\u{92}\u{8f}\u{96}\u{96}\u{99}J\u{a1}\u{99}\u{9c}\u{96}\u{8e}",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn yenc_handles_panics() {
+        // This tests if yEnc can handle panics
+        // It should return None
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn yenc_handles_panic_if_empty_string() {
+        // This tests if yEnc can handle an empty string
+        // It should return None
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn yenc_handles_panic_if_emoji() {
+        // This tests if yEnc can handle an emoji
+        // It should return None
+        let decoder = Decoder::<YEncDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_yenc("hello\tworld\n");
+        assert!(encoded.starts_with("=ybegin line=128 size=12 name=data\n"));
+        assert!(encoded.ends_with("\n=yend size=12"));
+        assert_eq!(
+            decode_yenc_no_error_handling(&encoded).unwrap(),
+            "hello\tworld\n".as_bytes()
+        );
+        let long_text = "yEnc ".repeat(60);
+        assert_eq!(
+            decode_yenc_no_error_handling(&encode_yenc(&long_text)).unwrap(),
+            long_text.as_bytes()
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! Extract a message hidden with zero-width characters
+//! Performs error handling and returns a string
+//! Call zero_width_decoder.crack to use. It returns option<String> and check with
+//! `result.is_some()` to see if it returned okay.
+
+use crate::checkers::CheckerTypes;
+
+use super::binary_decoder::binary_to_string;
+use super::crack_results::CrackResult;
+use super::interface::Crack;
+use super::interface::Decoder;
+
+use log::{debug, info, trace};
+
+/// The zero-width decoder, call:
+/// `let zero_width_decoder = Decoder::<ZeroWidthDecoder>::new()` to create a new instance
+/// And then call:
+/// `result = zero_width_decoder.crack(input)` to extract a hidden zero-width message
+/// The struct generated by new() comes from interface.rs
+/// ```
+/// use ares::decoders::zero_width_decoder::{ZeroWidthDecoder};
+/// use ares::decoders::interface::{Crack, Decoder};
+/// use ares::checkers::{athena::Athena, CheckerTypes, checker_type::{Check, Checker}};
+///
+/// let decode_zero_width = Decoder::<ZeroWidthDecoder>::new();
+/// let athena_checker = Checker::<Athena>::new();
+/// let checker = CheckerTypes::CheckAthena(athena_checker);
+///
+/// // "hi" is 01101000 01101001, with U+200B as 0 and U+200C as 1
+/// let text = "Nothing to see\u{200b}\u{200c}\u{200c}\u{200b}\u{200c}\u{200b}\u{200b}\u{200b}\u{200b}\u{200c}\u{200c}\u{200b}\u{200c}\u{200b}\u{200b}\u{200c} here";
+/// let result = decode_zero_width.crack(text, &checker).unencrypted_text;
+/// assert!(result.is_some());
+/// assert_eq!(result.unwrap()[0], "hi");
+/// ```
+pub struct ZeroWidthDecoder;
+
+impl Crack for Decoder<ZeroWidthDecoder> {
+    fn new() -> Decoder<ZeroWidthDecoder> {
+        Decoder {
+            name: "Zero-width steganography",
+            description: "Zero-width characters such as the zero-width space, joiner and non-joiner are invisible when rendered, so a sequence of them can hide a binary message inside otherwise normal looking text.",
+            link: "https://en.wikipedia.org/wiki/Zero-width_space",
+            tags: vec!["zero_width", "steganography", "unicode", "decoder"],
+            popularity: 0.4,
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// This function does the actual decoding
+    /// It returns an Option<string> if it was successful
+    /// Else the Option returns nothing and the error is logged in Trace
+    fn crack(&self, text: &str, checker: &CheckerTypes) -> CrackResult {
+        trace!("Trying zero-width steganography with text {:?}", text);
+        let mut results = CrackResult::new(self, text.to_string());
+        let decoded_strings = decode_zero_width_no_error_handling(text);
+
+        if decoded_strings.is_none() {
+            debug!("Failed to decode zero-width steganography because ZeroWidthDecoder::decode_zero_width_no_error_handling returned None");
+            return results;
+        }
+
+        // Each way of reading the bits is a candidate, like the shifts in caesar
+        let decoded_strings = decoded_strings.unwrap();
+        for decoded_text in decoded_strings.iter() {
+            let checker_result = checker.check(decoded_text);
+            if checker_result.is_identified {
+                info!("Found a zero-width message {}", decoded_text);
+                results.unencrypted_text = Some(vec![decoded_text.to_string()]);
+                results.update_checker(&checker_result);
+                return results;
+            }
+        }
+        results.unencrypted_text = Some(decoded_strings);
+        results
+    }
+    /// Gets all tags for this decoder
+    fn get_tags(&self) -> &Vec<&str> {
+        &self.tags
+    }
+    /// Gets the name for the current decoder
+    fn get_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// The invisible characters tools use to hide bits
+const ZERO_WIDTH_CHARACTERS: [char; 8] = [
+    '\u{200b}', // zero-width space
+    '\u{200c}', // zero-width non-joiner
+    '\u{200d}', // zero-width joiner
+    '\u{200e}', // left-to-right mark
+    '\u{200f}', // right-to-left mark
+    '\u{2060}', // word joiner
+    '\u{feff}', // zero-width no-break space
+    '\u{180e}', // mongolian vowel separator
+];
+
+/// Returns true if the text has a zero-width message we can decode.
+/// Used so we don't stop at the innocent looking cover text.
+pub(crate) fn contains_zero_width_message(text: &str) -> bool {
+    decode_zero_width_no_error_handling(text).is_some()
+}
+
+/// helper function
+/// Two distinct characters are read as 0 and 1 in groups of 8 bits.
+/// With three, one of them separates the characters and the other two are the bits.
+/// We don't know which character means what, so we try every arrangement and keep
+/// the ones that decode to printable text.
+fn decode_zero_width_no_error_handling(text: &str) -> Option<Vec<String>> {
+    let symbols: Vec<char> = text
+        .chars()
+        .filter(|c| ZERO_WIDTH_CHARACTERS.contains(c))
+        .collect();
+    if symbols.len() < 8 {
+        return None;
+    }
+    let mut distinct: Vec<char> = Vec::new();
+    for symbol in &symbols {
+        if !distinct.contains(symbol) {
+            distinct.push(*symbol);
+        }
+    }
+
+    let mut candidates: Vec<String> = Vec::new();
+    match distinct.len() {
+        2 => {
+            for (zero, one) in [(distinct[0], distinct[1]), (distinct[1], distinct[0])] {
+                let bits = symbols_to_bits(&symbols, zero, one);
+                let whole_bytes = bits.len() - bits.len() % 8;
+                candidates.push(binary_to_string(&bits[..whole_bytes], 8));
+            }
+        }
+        3 => {
+            for separator in distinct.iter() {
+                let bit_symbols: Vec<char> = distinct
+                    .iter()
+                    .filter(|c| *c != separator)
+                    .copied()
+                    .collect();
+                for (zero, one) in [
+                    (bit_symbols[0], bit_symbols[1]),
+                    (bit_symbols[1], bit_symbols[0]),
+                ] {
+                    let decoded: Option<String> = symbols
+                        .split(|c| c == separator)
+                        .filter(|group| !group.is_empty())
+                        .map(|group| {
+                            let bits = symbols_to_bits(group, zero, one);
+                            char::from_u32(u32::from_str_radix(&bits, 2).ok()?)
+                        })
+                        .collect();
+                    if let Some(decoded) = decoded {
+                        candidates.push(decoded);
+                    }
+                }
+            }
+        }
+        _ => return None,
+    }
+
+    candidates.retain(|candidate| is_printable(candidate));
+    candidates.dedup();
+    if candidates.is_empty() {
+        return None;
+    }
+    Some(candidates)
+}
+
+/// Maps zero-width symbols to a string of '0' and '1'
+fn symbols_to_bits(symbols: &[char], zero: char, one: char) -> String {
+    symbols
+        .iter()
+        .filter_map(|c| {
+            if *c == zero {
+                Some('0')
+            } else if *c == one {
+                Some('1')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Random bits almost always decode to control characters, so we only keep
+/// candidates which are entirely printable
+pub(crate) fn is_printable(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| !c.is_control() || c == '\n' || c == '\r' || c == '\t')
+}
+
+/// Hides the text in zero-width characters after the first character of the cover text,
+/// a zero-width space for each 0 bit and a zero-width non-joiner for each 1 bit
+pub(crate) fn encode_zero_width(text: &str, cover: &str) -> String {
+    let bits: String = text
+        .bytes()
+        .map(|byte| format!("{byte:08b}"))
+        .collect::<String>()
+        .replace('0', "\u{200b}")
+        .replace('1', "\u{200c}");
+    // Zero-width characters already in the cover would scramble the message
+    let mut cover = cover.chars().filter(|c| !ZERO_WIDTH_CHARACTERS.contains(c));
+    let first: String = cover.next().into_iter().collect();
+    format!("{first}{bits}{}", cover.collect::<String>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        contains_zero_width_message, decode_zero_width_no_error_handling, encode_zero_width,
+        ZeroWidthDecoder,
+    };
+    use crate::{
+        checkers::{
+            athena::Athena,
+            checker_type::{Check, Checker},
+            CheckerTypes,
+        },
+        decoders::interface::{Crack, Decoder},
+    };
+
+    // helper for tests
+    fn get_athena_checker() -> CheckerTypes {
+        let athena_checker = Checker::<Athena>::new();
+        CheckerTypes::CheckAthena(athena_checker)
+    }
+
+    // Hides each byte of the message as 8 zero-width characters
+    fn hide(message: &str, zero: char, one: char) -> String {
+        message
+            .bytes()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1))
+            .map(|bit| if bit == 1 { one } else { zero })
+            .collect()
+    }
+
+    #[test]
+    fn zero_width_decodes_successfully() {
+        // The hidden URL is found between two words of the cover text
+        let decoder = Decoder::<ZeroWidthDecoder>::new();
+        let text = format!(
+            "Nothing{} to see here",
+            hide("https://www.google.com", '\u{200c}', '\u{200d}')
+        );
+        let result = decoder.crack(&text, &get_athena_checker());
+        assert!(result.success);
+        assert_eq!(
+            result.unencrypted_text.unwrap()[0],
+            "https://www.google.com"
+        );
+    }
+
+    #[test]
+    fn zero_width_decodes_with_separator() {
+        // Each character is a variable length binary number separated by U+200D
+        let decoder = Decoder::<ZeroWidthDecoder>::new();
+        // 'h' is 1101000 and 'i' is 1101001
+        let text = "hello\u{200c}\u{200c}\u{200b}\u{200c}\u{200b}\u{200b}\u{200b}\u{200d}\u{200c}\u{200c}\u{200b}\u{200c}\u{200b}\u{200b}\u{200c} world";
+        let result = decoder
+            .crack(text, &get_athena_checker())
+            .unencrypted_text
+            .unwrap();
+        assert!(result.contains(&"hi".to_string()));
+    }
+
+    #[test]
+    fn zero_width_detects_hidden_message() {
+        let text = format!("cover text{}", hide("secret", '\u{200b}', '\u{feff}'));
+        assert!(contains_zero_width_message(&text));
+        assert!(!contains_zero_width_message("cover text"));
+    }
+
+    #[test]
+    fn zero_width_handles_too_few_characters() {
+        // A single stray zero-width space is not a message
+        // It should return None
+        let decoder = Decoder::<ZeroWidthDecoder>::new();
+        let result = decoder
+            .crack("hello\u{200b} world", &get_athena_checker())
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn zero_width_handles_panics() {
+        // This tests if zero-width can handle panics
+        // It should return None
+        let decoder = Decoder::<ZeroWidthDecoder>::new();
+        let result = decoder
+            .crack(
+                "hello my name is panicky mc panic face!",
+                &get_athena_checker(),
+            )
+            .unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn zero_width_handles_panic_if_empty_string() {
+        // This tests if zero-width can handle an empty string
+        // It should return None
+        let decoder = Decoder::<ZeroWidthDecoder>::new();
+        let result = decoder.crack("", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn zero_width_handles_panic_if_emoji() {
+        // This tests if zero-width can handle an emoji
+        // It should return None
+        let decoder = Decoder::<ZeroWidthDecoder>::new();
+        let result = decoder.crack("😂", &get_athena_checker()).unencrypted_text;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let encoded = encode_zero_width("hidden", "Nothing\u{200b} to see here");
+        assert!(encoded.starts_with('N'));
+        assert_eq!(
+            encoded.chars().filter(|c| c.is_ascii()).collect::<String>(),
+            "Nothing to see here"
+        );
+        assert_eq!(
+            decode_zero_width_no_error_handling(&encoded).unwrap()[0],
+            "hidden"
+        );
+    }
+}
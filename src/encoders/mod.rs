@@ -0,0 +1,526 @@
+//! Encoders are the reverse of our decoders, so we can build layered test inputs
+//! like `hex(base64(rot13(x)))` and check Ares can crack them.
+//! Each encoder lives next to the decoder it reverses, this module lists them
+//! and chains them together.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::decoders::{
+    a1z26_decoder::encode_a1z26,
+    atbash_decoder::atbash_to_alphabet,
+    base32_decoder::encode_base32,
+    base58_bitcoin_decoder::encode_base58_bitcoin,
+    base58_flickr_decoder::encode_base58_flickr,
+    base58_monero_decoder::encode_base58_monero,
+    base58_ripple_decoder::encode_base58_ripple,
+    base64_decoder::encode_base64,
+    base64_url_decoder::encode_base64_url,
+    base65536_decoder::encode_base65536,
+    base91_decoder::encode_base91,
+    binary_decoder::encode_binary,
+    caesar_decoder::encode_caesar,
+    citrix_ctx1_decoder::encode_citrix_ctx1,
+    escape_sequence_decoder::encode_escape_sequences,
+    hexadecimal_decoder::encode_hexadecimal,
+    html_entity_decoder::encode_html_entities,
+    jwt_decoder::encode_jwt,
+    morse_code::encode_morse,
+    punycode_decoder::encode_punycode_labels,
+    quoted_printable_decoder::encode_quoted_printable,
+    railfence_decoder::{parse_railfence_key, railfence_encoder},
+    reverse_decoder::encode_reverse,
+    url_decoder::encode_url,
+    uuencode_decoder::encode_uuencode,
+    whitespace_decoder::encode_whitespace,
+    xxencode_decoder::encode_xxencode,
+    yenc_decoder::encode_yenc,
+    zero_width_decoder::encode_zero_width,
+};
+
+/// An encoder turns text into something one of our decoders can decode
+pub struct Encoder {
+    /// The name used in chains, like `base64`
+    pub name: &'static str,
+    /// The name of the decoder which reverses it
+    pub decoder: &'static str,
+    /// A short description of what it does
+    pub description: &'static str,
+    /// What the key is, for encoders which take one, like the shift for caesar.
+    /// Keys go after the name in chains, like `caesar:3`
+    pub key: Option<&'static str>,
+    /// The key its decoder needs to reverse it, for encoders like `rot13` which
+    /// are another encoder with the key fixed. Recipes use it when no key is given.
+    pub decoder_key: Option<&'static str>,
+    /// Encodes the text, with the key if one was given
+    encode: fn(&str, Option<&str>) -> Result<String, String>,
+}
+
+impl Encoder {
+    /// Encodes the text, with the key if one was given
+    /// # Errors
+    /// Returns an error if the text can't be encoded (say morse code for an emoji) or the key is invalid.
+    pub fn encode(&self, text: &str, key: Option<&str>) -> Result<String, String> {
+        (self.encode)(text, key)
+    }
+}
+
+/// Every encoder, in the same order as the decoders are listed
+pub static ENCODERS: [Encoder; 30] = [
+    Encoder {
+        name: "reverse",
+        decoder: "Reverse",
+        description: "Reverses the text",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_reverse(text)),
+    },
+    Encoder {
+        name: "base64",
+        decoder: "Base64",
+        description: "Padded base64",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base64(text)),
+    },
+    Encoder {
+        name: "base64_url",
+        decoder: "Base64 URL",
+        description: "URL safe base64 without padding",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base64_url(text)),
+    },
+    Encoder {
+        name: "base58_bitcoin",
+        decoder: "Base58 Bitcoin",
+        description: "Base58 with the Bitcoin alphabet",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base58_bitcoin(text)),
+    },
+    Encoder {
+        name: "base58_monero",
+        decoder: "Base58 Monero",
+        description: "Base58 with the Monero alphabet",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base58_monero(text)),
+    },
+    Encoder {
+        name: "base58_ripple",
+        decoder: "Base58 Ripple",
+        description: "Base58 with the Ripple alphabet",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base58_ripple(text)),
+    },
+    Encoder {
+        name: "base58_flickr",
+        decoder: "Base58 Flickr",
+        description: "Base58 with the Flickr alphabet",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base58_flickr(text)),
+    },
+    Encoder {
+        name: "base91",
+        decoder: "Base91",
+        description: "Base91",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base91(text)),
+    },
+    Encoder {
+        name: "base65536",
+        decoder: "Base65536",
+        description: "Base65536, two bytes per character",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base65536(text)),
+    },
+    Encoder {
+        name: "binary",
+        decoder: "Binary",
+        description: "8 bits per byte, separated by spaces",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_binary(text)),
+    },
+    Encoder {
+        name: "hex",
+        decoder: "Hexadecimal",
+        description: "Two lowercase hexadecimal digits per byte",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_hexadecimal(text)),
+    },
+    Encoder {
+        name: "base32",
+        decoder: "Base32",
+        description: "Padded base32",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_base32(text)),
+    },
+    Encoder {
+        name: "morse",
+        decoder: "Morse Code",
+        description: "Morse code, with / between words",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| {
+            encode_morse(text)
+                .ok_or_else(|| "the text has characters morse code doesn't have".to_string())
+        },
+    },
+    Encoder {
+        name: "atbash",
+        decoder: "Atbash",
+        description: "Swaps each letter with its mirror in the alphabet, a with z and so on",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(atbash_to_alphabet(text)),
+    },
+    Encoder {
+        name: "caesar",
+        decoder: "Caesar Cipher",
+        description: "Shifts each letter forward through the alphabet",
+        key: Some("how far to shift each letter, 13 by default"),
+        decoder_key: None,
+        encode: |text, key| {
+            let shift = match key {
+                Some(key) => key
+                    .parse::<u8>()
+                    .map_err(|_| format!("the shift {key:?} isn't a number from 0 to 255"))?,
+                None => 13,
+            };
+            Ok(encode_caesar(text, shift))
+        },
+    },
+    Encoder {
+        name: "rot13",
+        decoder: "Caesar Cipher",
+        description: "Caesar with a shift of 13",
+        key: None,
+        decoder_key: Some("13"),
+        encode: |text, _| Ok(encode_caesar(text, 13)),
+    },
+    Encoder {
+        name: "railfence",
+        decoder: "Railfence",
+        description: "Writes the text in a zigzag over the rails and reads off each rail",
+        key: Some("the number of rails, 3 by default, and optionally the offset like 3:1"),
+        decoder_key: None,
+        encode: |text, key| {
+            let (rails, offset) = parse_railfence_key(key.unwrap_or("3"))?;
+            Ok(railfence_encoder(text, rails, offset))
+        },
+    },
+    Encoder {
+        name: "citrix_ctx1",
+        decoder: "Citrix Ctx1",
+        description: "Citrix CTX1 password obfuscation",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_citrix_ctx1(text)),
+    },
+    Encoder {
+        name: "url",
+        decoder: "URL",
+        description: "Percent-encodes everything but letters, digits and -._~",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_url(text)),
+    },
+    Encoder {
+        name: "a1z26",
+        decoder: "A1Z26",
+        description: "Each letter as its place in the alphabet, like 1 for a",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| {
+            encode_a1z26(text).ok_or_else(|| "A1Z26 can only encode letters".to_string())
+        },
+    },
+    Encoder {
+        name: "escape_sequence",
+        decoder: "Escape Sequence",
+        description: "Every byte as a \\xHH escape sequence",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_escape_sequences(text)),
+    },
+    Encoder {
+        name: "html_entity",
+        decoder: "HTML Entity",
+        description: "Every character as a decimal HTML entity",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_html_entities(text)),
+    },
+    Encoder {
+        name: "quoted_printable",
+        decoder: "Quoted-printable",
+        description: "Quoted-printable, as used in emails",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_quoted_printable(text)),
+    },
+    Encoder {
+        name: "uuencode",
+        decoder: "UUencode",
+        description: "Uuencode with begin and end lines",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_uuencode(text)),
+    },
+    Encoder {
+        name: "xxencode",
+        decoder: "XXencode",
+        description: "Xxencode with begin and end lines",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_xxencode(text)),
+    },
+    Encoder {
+        name: "yenc",
+        decoder: "yEnc",
+        description: "yEnc with =ybegin and =yend lines",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| Ok(encode_yenc(text)),
+    },
+    Encoder {
+        name: "punycode",
+        decoder: "Punycode",
+        description: "Punycode for each word with non-ASCII letters, like domain names",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| {
+            encode_punycode_labels(text)
+                .ok_or_else(|| "punycode needs some non-ASCII letters to encode".to_string())
+        },
+    },
+    Encoder {
+        name: "jwt",
+        decoder: "JWT",
+        description: "An unsigned JWT with the text as its claims",
+        key: None,
+        decoder_key: None,
+        encode: |text, _| {
+            encode_jwt(text).ok_or_else(|| "JWT claims have to be a JSON object".to_string())
+        },
+    },
+    Encoder {
+        name: "whitespace",
+        decoder: "Whitespace steganography",
+        description: "Hides the text in trailing spaces and tabs",
+        key: Some("the cover text to hide it after, none by default"),
+        decoder_key: None,
+        encode: |text, cover| Ok(encode_whitespace(text, cover.unwrap_or_default())),
+    },
+    Encoder {
+        name: "zero_width",
+        decoder: "Zero-width steganography",
+        description: "Hides the text in zero-width characters",
+        key: Some("the cover text to hide it in, none by default"),
+        decoder_key: None,
+        encode: |text, cover| Ok(encode_zero_width(text, cover.unwrap_or_default())),
+    },
+];
+
+/// Finds an encoder by its name, or the name of the decoder it reverses.
+/// Case, spaces and dashes don't matter, so `Base64 URL` finds `base64_url`.
+pub fn get_encoder(name: &str) -> Option<&'static Encoder> {
+    let name = normalise_name(name);
+    ENCODERS
+        .iter()
+        .find(|encoder| encoder.name == name)
+        .or_else(|| {
+            ENCODERS
+                .iter()
+                .find(|encoder| normalise_name(encoder.decoder) == name)
+        })
+}
+
+/// Lowercases the name and turns spaces and dashes into underscores
+fn normalise_name(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+/// One encoder in a chain, with its key if it has one
+#[derive(Clone)]
+pub struct EncoderStep {
+    /// The encoder to run
+    pub encoder: &'static Encoder,
+    /// The key to run it with
+    pub key: Option<String>,
+}
+
+/// Encoders to run one after the other, written like `rot13,base64,hex`.
+/// Keys go after a colon, like `caesar:3,railfence:4`.
+#[derive(Clone)]
+pub struct EncoderChain {
+    /// The encoders, in the order they run
+    pub steps: Vec<EncoderStep>,
+}
+
+impl FromStr for EncoderChain {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let steps = input
+            .split(',')
+            .map(|step| {
+                let (name, key) = match step.split_once(':') {
+                    Some((name, key)) => (name, Some(key.to_string())),
+                    None => (step, None),
+                };
+                let encoder = get_encoder(name).ok_or_else(|| {
+                    format!(
+                        "there's no encoder called {:?}, try one of {}",
+                        name.trim(),
+                        ENCODERS
+                            .iter()
+                            .map(|encoder| encoder.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })?;
+                if key.is_some() && encoder.key.is_none() {
+                    return Err(format!("{} doesn't take a key", encoder.name));
+                }
+                Ok(EncoderStep { encoder, key })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(EncoderChain { steps })
+    }
+}
+
+impl fmt::Display for EncoderChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps: Vec<String> = self
+            .steps
+            .iter()
+            .map(|step| match &step.key {
+                Some(key) => format!("{}:{key}", step.encoder.name),
+                None => step.encoder.name.to_string(),
+            })
+            .collect();
+        write!(f, "{}", steps.join(","))
+    }
+}
+
+impl EncoderChain {
+    /// Runs each encoder on the output of the one before it
+    /// # Errors
+    /// Returns an error naming the encoder which couldn't encode its input.
+    pub fn encode(&self, text: &str) -> Result<String, String> {
+        self.steps.iter().try_fold(text.to_string(), |text, step| {
+            step.encoder
+                .encode(&text, step.key.as_deref())
+                .map_err(|error| format!("{} failed: {error}", step.encoder.name))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_encoder, EncoderChain, ENCODERS};
+    use crate::checkers::{
+        athena::Athena,
+        checker_type::{Check, Checker},
+        CheckerTypes,
+    };
+    use crate::registry;
+
+    #[test]
+    fn test_every_encoder_has_a_decoder() {
+        for encoder in &ENCODERS {
+            assert!(
+                registry::decoder(encoder.decoder).is_some(),
+                "{} has no decoder",
+                encoder.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_encoder_by_decoder_name() {
+        assert_eq!(get_encoder("Base64 URL").unwrap().name, "base64_url");
+        assert_eq!(get_encoder("HEX").unwrap().name, "hex");
+        assert_eq!(get_encoder("Caesar Cipher").unwrap().name, "caesar");
+        assert!(get_encoder("rot47").is_none());
+    }
+
+    #[test]
+    fn test_chain_encodes_in_order() {
+        let chain: EncoderChain = "rot13,base64,hex".parse().unwrap();
+        assert_eq!(chain.encode("hello").unwrap(), "64584a356557493d");
+        assert_eq!(chain.to_string(), "rot13,base64,hex");
+    }
+
+    #[test]
+    fn test_chain_keys() {
+        let chain: EncoderChain = "caesar:3, railfence:2".parse().unwrap();
+        assert_eq!(chain.encode("abcd").unwrap(), "dfeg");
+        assert_eq!(chain.to_string(), "caesar:3,railfence:2");
+        assert!("base64:3".parse::<EncoderChain>().is_err());
+        assert!("caesar:x"
+            .parse::<EncoderChain>()
+            .unwrap()
+            .encode("a")
+            .is_err());
+        assert!("railfence:1"
+            .parse::<EncoderChain>()
+            .unwrap()
+            .encode("a")
+            .is_err());
+    }
+
+    #[test]
+    fn test_chain_errors_name_the_encoder() {
+        assert!("base64,nope"
+            .parse::<EncoderChain>()
+            .err()
+            .unwrap()
+            .contains("\"nope\""));
+        let error = "base64,a1z26"
+            .parse::<EncoderChain>()
+            .unwrap()
+            .encode("hi")
+            .unwrap_err();
+        assert!(error.starts_with("a1z26 failed"));
+    }
+
+    #[test]
+    fn test_every_encoder_round_trips_through_its_decoder() {
+        // The checker doesn't matter, we only look at what each decoder gives back
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        for encoder in &ENCODERS {
+            // Each encoder needs text it can encode, and that its decoder can tell apart
+            let plaintext = match encoder.name {
+                "a1z26" | "morse" => "HELLO",
+                "jwt" => r#"{"sub":"hello"}"#,
+                "punycode" => "bücher",
+                // Quoted-printable only decodes text with something escaped
+                "quoted_printable" => "hello = world",
+                _ => "hello world",
+            };
+            let encoded = encoder.encode(plaintext, None).unwrap();
+            let decoder = registry::decoder(encoder.decoder).unwrap();
+            let decoded = decoder
+                .crack(&encoded, &checker)
+                .unencrypted_text
+                .unwrap_or_default();
+            assert!(
+                decoded.iter().any(|text| text.contains(plaintext)),
+                "{} gave {:?} back from {:?}",
+                encoder.name,
+                decoded,
+                encoded
+            );
+        }
+    }
+}
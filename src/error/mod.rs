@@ -0,0 +1,184 @@
+//! The errors Ares can stop with, and the exit code for each of them.
+//! The CLI prints them with `cli_pretty_printing::program_exiting_with_error`
+//! and exits with `Error::exit_code`, so scripts can tell what happened.
+
+use std::{fmt, io};
+
+use crate::recipe::RecipeFailure;
+
+/// We decoded the text, or did what we were asked
+pub const EXIT_SUCCESS: u8 = 0;
+/// The search (or recipe) didn't find the plaintext
+pub const EXIT_NOT_FOUND: u8 = 1;
+/// We were given input we can't use, the same code clap uses for bad arguments
+pub const EXIT_BAD_INPUT: u8 = 2;
+/// The search ran out of time before it found the plaintext
+pub const EXIT_TIMEOUT: u8 = 3;
+/// Something else went wrong, like the output file couldn't be written
+pub const EXIT_FAILURE: u8 = 4;
+
+/// Why Ares couldn't do what it was asked
+#[derive(Debug)]
+pub enum Error {
+    /// Both `--file` and `--text` were given, so we don't know which to decode
+    BothFileAndText,
+    /// No text or file was given and nothing was piped into stdin
+    NoInput,
+    /// The file given with `--file` couldn't be read
+    CouldNotReadFile {
+        /// The file we tried to read
+        path: String,
+        /// Why we couldn't read it
+        error: io::Error,
+    },
+    /// Stdin couldn't be read
+    CouldNotReadStdin(io::Error),
+    /// A wordlist given with `--wordlist` couldn't be read
+    CouldNotReadWordlist(io::Error),
+    /// The regex given with `--regex` isn't a valid regex
+    InvalidRegex {
+        /// The regex we were given
+        regex: String,
+        /// What's wrong with it
+        error: String,
+    },
+    /// A config file couldn't be read, or asked for a profile we don't have
+    BadConfigFile(String),
+    /// One of the encoders in `ares encode` couldn't encode its input
+    CouldNotEncode(String),
+    /// `ares explain` was given a name that isn't a decoder or checker
+    UnknownComponent(String),
+    /// A step of the `--recipe` couldn't decode its input
+    RecipeFailed(RecipeFailure),
+    /// The search finished (or was stopped) without finding the plaintext
+    NotFound,
+    /// The search ran out of time before it found the plaintext
+    Timeout {
+        /// How long the search had
+        seconds: u32,
+    },
+    /// We decoded the text but couldn't write it to the output file
+    CouldNotWriteOutput {
+        /// The file we tried to write
+        path: String,
+        /// Why we couldn't write it
+        error: io::Error,
+    },
+    /// The search tree explorer couldn't take over the terminal
+    Tui(io::Error),
+}
+
+impl Error {
+    /// The code the program exits with for this error.
+    /// Bad input is 2, not finding the plaintext is 1 and timing out is 3.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::NotFound | Error::RecipeFailed(_) => EXIT_NOT_FOUND,
+            Error::Timeout { .. } => EXIT_TIMEOUT,
+            Error::BothFileAndText
+            | Error::NoInput
+            | Error::CouldNotReadFile { .. }
+            | Error::CouldNotReadStdin(_)
+            | Error::CouldNotReadWordlist(_)
+            | Error::InvalidRegex { .. }
+            | Error::BadConfigFile(_)
+            | Error::CouldNotEncode(_)
+            | Error::UnknownComponent(_) => EXIT_BAD_INPUT,
+            Error::CouldNotWriteOutput { .. } | Error::Tui(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BothFileAndText => {
+                write!(f, "Both a file and text were given, please only use one.")
+            }
+            Error::NoInput => write!(
+                f,
+                "No input was given. Please use -t for text, -f for files or pipe it into stdin."
+            ),
+            Error::CouldNotReadFile { path, error } => {
+                write!(f, "Could not read the file {path:?}: {error}")
+            }
+            Error::CouldNotReadStdin(error) => {
+                write!(f, "Could not read the input from stdin: {error}")
+            }
+            Error::CouldNotReadWordlist(error) => {
+                write!(f, "Could not read the wordlist: {error}")
+            }
+            Error::InvalidRegex { regex, error } => {
+                write!(f, "The regex {regex:?} isn't valid: {error}")
+            }
+            Error::BadConfigFile(error) => write!(f, "Could not use the config file: {error}"),
+            Error::CouldNotEncode(error) => write!(f, "Could not encode the text: {error}"),
+            Error::UnknownComponent(name) => write!(
+                f,
+                "There's no decoder or checker called {name:?}, see `ares list decoders` and `ares list checkers`"
+            ),
+            Error::RecipeFailed(failure) => write!(f, "The recipe failed at {failure}."),
+            Error::NotFound => write!(f, "Ares has failed to decode the text."),
+            Error::Timeout { seconds } => write!(
+                f,
+                "Ares ran out of time after {seconds} seconds. Try a longer --cracking-timeout."
+            ),
+            Error::CouldNotWriteOutput { path, error } => {
+                write!(f, "Could not write the plaintext to {path:?}: {error}")
+            }
+            Error::Tui(error) => write!(f, "Could not open the search tree explorer: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::CouldNotReadFile { error, .. }
+            | Error::CouldNotWriteOutput { error, .. }
+            | Error::CouldNotReadStdin(error)
+            | Error::CouldNotReadWordlist(error)
+            | Error::Tui(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, EXIT_BAD_INPUT, EXIT_FAILURE, EXIT_NOT_FOUND, EXIT_SUCCESS, EXIT_TIMEOUT};
+    use std::io;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        assert_eq!(Error::NotFound.exit_code(), EXIT_NOT_FOUND);
+        assert_eq!(Error::Timeout { seconds: 5 }.exit_code(), EXIT_TIMEOUT);
+        assert_eq!(Error::NoInput.exit_code(), EXIT_BAD_INPUT);
+        assert_eq!(Error::BothFileAndText.exit_code(), EXIT_BAD_INPUT);
+        assert_eq!(
+            Error::Tui(io::Error::other("no terminal")).exit_code(),
+            EXIT_FAILURE
+        );
+        let mut codes = [EXIT_NOT_FOUND, EXIT_BAD_INPUT, EXIT_TIMEOUT, EXIT_FAILURE];
+        codes.sort_unstable();
+        assert!(codes[0] > EXIT_SUCCESS);
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_messages_say_what_went_wrong() {
+        let error = Error::CouldNotReadFile {
+            path: "missing.txt".to_string(),
+            error: io::Error::new(io::ErrorKind::NotFound, "No such file or directory"),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Could not read the file \"missing.txt\": No such file or directory"
+        );
+        assert!(std::error::Error::source(&error).is_some());
+        assert_eq!(
+            Error::Timeout { seconds: 5 }.to_string(),
+            "Ares ran out of time after 5 seconds. Try a longer --cracking-timeout."
+        );
+    }
+}
@@ -3,31 +3,10 @@
 
 use std::sync::mpsc::channel;
 
-use crate::checkers::CheckerTypes;
-use crate::decoders::atbash_decoder::AtbashDecoder;
-use crate::decoders::base32_decoder::Base32Decoder;
-use crate::decoders::base58_bitcoin_decoder::Base58BitcoinDecoder;
-use crate::decoders::base58_monero_decoder::Base58MoneroDecoder;
-use crate::decoders::binary_decoder::BinaryDecoder;
-use crate::decoders::hexadecimal_decoder::HexadecimalDecoder;
-use crate::DecoderResult;
-
-use crate::decoders::base58_flickr_decoder::Base58FlickrDecoder;
-use crate::decoders::base58_ripple_decoder::Base58RippleDecoder;
-
-use crate::decoders::a1z26_decoder::A1Z26Decoder;
-use crate::decoders::base64_decoder::Base64Decoder;
-use crate::decoders::base64_url_decoder::Base64URLDecoder;
-use crate::decoders::base65536_decoder::Base65536Decoder;
-use crate::decoders::base91_decoder::Base91Decoder;
-use crate::decoders::caesar_decoder::CaesarDecoder;
-use crate::decoders::citrix_ctx1_decoder::CitrixCTX1Decoder;
+use crate::checkers::{human_checker, CheckerTypes};
 use crate::decoders::crack_results::CrackResult;
-use crate::decoders::interface::{Crack, Decoder};
-use crate::decoders::morse_code::MorseCodeDecoder;
-use crate::decoders::railfence_decoder::RailfenceDecoder;
-use crate::decoders::reverse_decoder::ReverseDecoder;
-use crate::decoders::url_decoder::URLDecoder;
+use crate::registry::{self, RegisteredDecoder};
+use crate::DecoderResult;
 
 use log::trace;
 use rayon::prelude::*;
@@ -40,7 +19,7 @@ use rayon::prelude::*;
 /// Relevant docs: https://doc.rust-lang.org/book/ch17-02-trait-objects.html
 pub struct Decoders {
     /// Components is a vector of decoders.
-    pub components: Vec<Box<dyn Crack + Sync>>,
+    pub components: Vec<&'static RegisteredDecoder>,
 }
 
 impl Decoders {
@@ -54,9 +33,13 @@ impl Decoders {
     pub fn run(&self, text: &str, checker: CheckerTypes) -> MyResults {
         trace!("Running .crack() on all decoders");
         let (sender, receiver) = channel();
+        // The decoders run on other threads, so they're told which search they're in
+        let search = human_checker::current_search();
         self.components
-            .into_par_iter()
+            .par_iter()
             .try_for_each_with(sender, |s, i| {
+                // So the human checker can say which decoder made the text
+                human_checker::set_current_decoder(i.get_name(), search.clone());
                 let results = i.crack(text, &checker);
                 if results.success {
                     s.send(results).expect("expected no send error!");
@@ -70,16 +53,29 @@ impl Decoders {
             });
 
         let mut all_results: Vec<CrackResult> = Vec::new();
+        let mut best_success: Option<CrackResult> = None;
 
         while let Ok(result) = receiver.recv() {
-            // if we recv success, break.
+            // Decoders run in parallel, so more than one may succeed before we stop.
+            // Keep the one the checkers were most confident in. If the human accepted one
+            // the human checker turns down the rest, so that one is kept.
             if result.success {
-                return MyResults::Break(result);
+                let more_confident = match &best_success {
+                    Some(best) => result.confidence > best.confidence,
+                    None => true,
+                };
+                if more_confident {
+                    best_success = Some(result);
+                }
+                continue;
             }
             all_results.push(result)
         }
 
-        MyResults::Continue(all_results)
+        match best_success {
+            Some(result) => MyResults::Break(Box::new(result)),
+            None => MyResults::Continue(all_results),
+        }
     }
 }
 
@@ -88,7 +84,7 @@ impl Decoders {
 /// else we return `Continue` with the decoded results.
 pub enum MyResults {
     /// Variant containing successful [`CrackResult`]
-    Break(CrackResult),
+    Break(Box<CrackResult>),
     /// Contains [`Vec`] of [`CrackResult`] for further processing
     Continue(Vec<CrackResult>),
 }
@@ -98,56 +94,18 @@ impl MyResults {
     /// as we aren't using it, it's just used in tests
     pub fn _break_value(self) -> Option<CrackResult> {
         match self {
-            MyResults::Break(val) => Some(val),
+            MyResults::Break(val) => Some(*val),
             MyResults::Continue(_) => None,
         }
     }
 }
 
 /// Currently takes no args as this is just a spike to get all the basic functionality working
+/// The decoders come from the [registry](crate::registry), which lists every decoder we have.
 pub fn filter_and_get_decoders(_text_struct: &DecoderResult) -> Decoders {
     trace!("Filtering and getting all decoders");
-    let binary = Decoder::<BinaryDecoder>::new();
-    let hexadecimal = Decoder::<HexadecimalDecoder>::new();
-    let base58_bitcoin = Decoder::<Base58BitcoinDecoder>::new();
-    let base58_monero = Decoder::<Base58MoneroDecoder>::new();
-    let base58_ripple = Decoder::<Base58RippleDecoder>::new();
-    let base58_flickr = Decoder::<Base58FlickrDecoder>::new();
-    let base64 = Decoder::<Base64Decoder>::new();
-    let base91 = Decoder::<Base91Decoder>::new();
-    let base64_url = Decoder::<Base64URLDecoder>::new();
-    let base65536 = Decoder::<Base65536Decoder>::new();
-    let citrix_ctx1 = Decoder::<CitrixCTX1Decoder>::new();
-    let url = Decoder::<URLDecoder>::new();
-    let base32 = Decoder::<Base32Decoder>::new();
-    let reversedecoder = Decoder::<ReverseDecoder>::new();
-    let morsecodedecoder = Decoder::<MorseCodeDecoder>::new();
-    let atbashdecoder = Decoder::<AtbashDecoder>::new();
-    let caesardecoder = Decoder::<CaesarDecoder>::new();
-    let railfencedecoder = Decoder::<RailfenceDecoder>::new();
-    let a1z26decoder = Decoder::<A1Z26Decoder>::new();
     Decoders {
-        components: vec![
-            Box::new(reversedecoder),
-            Box::new(base64),
-            Box::new(base58_bitcoin),
-            Box::new(base58_monero),
-            Box::new(base58_ripple),
-            Box::new(base58_flickr),
-            Box::new(base91),
-            Box::new(base65536),
-            Box::new(binary),
-            Box::new(hexadecimal),
-            Box::new(base32),
-            Box::new(morsecodedecoder),
-            Box::new(atbashdecoder),
-            Box::new(caesardecoder),
-            Box::new(railfencedecoder),
-            Box::new(citrix_ctx1),
-            Box::new(url),
-            Box::new(base64_url),
-            Box::new(a1z26decoder),
-        ],
+        components: registry::decoders().iter().map(Box::as_ref).collect(),
     }
 }
 
@@ -23,9 +23,17 @@ pub mod cli_pretty_printing;
 pub mod config;
 /// Decoders are the functions that actually perform the decodings.
 pub mod decoders;
+/// Encoders are the reverse of decoders, used to build test inputs.
+pub mod encoders;
+/// The errors Ares can stop with, and their exit codes
+pub mod error;
 /// The filtration system builds what decoders to use at runtime
 /// By default it will use them all.
 mod filtration_system;
+/// Recipes replay a known chain of decoders without searching
+pub mod recipe;
+/// The registry lists every decoder and checker
+pub mod registry;
 /// The searcher is the thing which searches for the plaintext
 /// It is the core of the program.
 mod searchers;
@@ -34,6 +42,9 @@ mod searchers;
 mod storage;
 /// Timer for internal use
 mod timer;
+/// The full-screen search tree explorer, behind the `tui` feature
+#[cfg(feature = "tui")]
+pub mod tui;
 
 use checkers::{
     athena::Athena,
@@ -41,8 +52,16 @@ use checkers::{
     checker_type::{Check, Checker},
 };
 use log::debug;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
 
-use crate::{config::Config, decoders::interface::Decoder};
+use crate::decoders::{
+    whitespace_decoder::contains_whitespace_message,
+    zero_width_decoder::contains_zero_width_message,
+};
+use crate::{config::Config, decoders::interface::Decoder, error::Error};
 
 use self::decoders::crack_results::CrackResult;
 /// The main function to call which performs the cracking.
@@ -57,7 +76,7 @@ use self::decoders::crack_results::CrackResult;
 /// config.verbose = 0;
 /// let result = perform_cracking("VGhlIG1haW4gZnVuY3Rpb24gdG8gY2FsbCB3aGljaCBwZXJmb3JtcyB0aGUgY3JhY2tpbmcu", config);
 /// assert!(true);
-/// // The result is an Option<DecoderResult> so we need to unwrap it
+/// // The result is a Result<DecoderResult, Error> so we need to unwrap it
 /// // The DecoderResult contains the text and the path
 /// // The path is a vector of CrackResults which contains the decoder used and the keys used
 /// // The text is a vector of strings because some decoders return more than 1 text (Caesar)
@@ -76,12 +95,65 @@ use self::decoders::crack_results::CrackResult;
 /// config.timeout = 0;
 /// let result = perform_cracking("VGhlIG1haW4gZnVuY3Rpb24gdG8gY2FsbCB3aGljaCBwZXJmb3JtcyB0aGUgY3JhY2tpbmcu", config);
 /// assert!(true);
-/// // If the program times out, or it cannot decode the text it will return None.
-/// assert!(result.is_none());
+/// // If the program times out, or it cannot decode the text it will return an error.
+/// assert!(result.is_err());
 /// ```
-pub fn perform_cracking(text: &str, config: Config) -> Option<DecoderResult> {
+/// # Errors
+/// Returns `Error::InvalidRegex` if the config's regex isn't valid,
+/// `Error::Timeout` if we run out of time and `Error::NotFound` if we can't decode the text.
+pub fn perform_cracking(text: &str, config: Config) -> Result<DecoderResult, Error> {
+    config.validate()?;
     config::set_global_config(config);
-    let text = text.to_string();
+    crack(text.to_string())
+}
+
+/// Cracks many texts at once, each with its own search and timeout.
+/// They are cracked in parallel and the results come back in the same order as the texts.
+/// ```rust
+/// use ares::perform_batch_cracking;
+/// use ares::config::Config;
+/// let mut config = Config::default();
+/// config.human_checker_on = false;
+/// let texts = vec!["aGVsbG8gdGhlcmUgZ2VuZXJhbA==".to_string(), "192.168.0.1".to_string()];
+/// let results = perform_batch_cracking(&texts, config).unwrap();
+/// assert!(results[0].as_ref().unwrap().text[0] == "hello there general");
+/// // The second text is already the plaintext
+/// assert!(results[1].as_ref().unwrap().text[0] == "192.168.0.1");
+/// ```
+/// # Errors
+/// Returns `Error::InvalidRegex` if the config's regex isn't valid.
+/// Each text has its own result, with the same errors as [`perform_cracking`].
+pub fn perform_batch_cracking(
+    texts: &[String],
+    config: Config,
+) -> Result<Vec<Result<DecoderResult, Error>>, Error> {
+    config.validate()?;
+    config::set_global_config(config);
+    // Each search already decodes in parallel on the rayon pool and waits on it,
+    // so the searches get their own threads rather than sharing that pool.
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |threads| threads.get())
+        .min(texts.len());
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Result<DecoderResult, Error>>> =
+        Mutex::new(texts.iter().map(|_| Err(Error::NotFound)).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(text) = texts.get(index) else {
+                    break;
+                };
+                let result = crack(text.clone());
+                results.lock().unwrap_or_else(|e| e.into_inner())[index] = result;
+            });
+        }
+    });
+    Ok(results.into_inner().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Cracks the text using the global config
+fn crack(text: String) -> Result<DecoderResult, Error> {
     let initial_check_for_plaintext = check_if_input_text_is_plaintext(&text);
     if initial_check_for_plaintext.is_identified {
         debug!(
@@ -91,14 +163,14 @@ pub fn perform_cracking(text: &str, config: Config) -> Option<DecoderResult> {
         cli_pretty_printing::return_early_because_input_text_is_plaintext();
 
         let mut crack_result = CrackResult::new(&Decoder::default(), text.to_string());
-        crack_result.checker_name = initial_check_for_plaintext.checker_name;
+        crack_result.update_checker(&initial_check_for_plaintext);
 
         let output = DecoderResult {
             text: vec![text],
             path: vec![crack_result],
         };
 
-        return Some(output);
+        return Ok(output);
     }
 
     // Build a new search tree
@@ -109,10 +181,53 @@ pub fn perform_cracking(text: &str, config: Config) -> Option<DecoderResult> {
     searchers::search_for_plaintext(text)
 }
 
+/// Encodes the text with a chain of encoders, the reverse of [`perform_cracking`].
+/// The chain is written like `rot13,base64,hex`, with keys after a colon like `caesar:3`.
+/// ```rust
+/// use ares::perform_encoding;
+/// let encoded = perform_encoding("hello", "rot13,base64,hex").unwrap();
+/// assert!(encoded == "64584a356557493d");
+/// ```
+/// # Errors
+/// Returns an error if the chain names an encoder we don't have,
+/// or an encoder can't encode its input.
+pub fn perform_encoding(text: &str, chain: &str) -> Result<String, String> {
+    chain.parse::<encoders::EncoderChain>()?.encode(text)
+}
+
+/// Decodes the text with a known chain of decoders instead of searching for one.
+/// ```rust
+/// use ares::perform_recipe;
+/// use ares::config::Config;
+/// use ares::recipe::Recipe;
+/// let recipe: Recipe = "base64,caesar:13,hex".parse().unwrap();
+/// let result = perform_recipe("NnI2czcw", &recipe, Config::default()).unwrap();
+/// assert!(result.text[0] == "nop");
+/// // If a step can't decode its input, we find out which one
+/// let failure = perform_recipe("not base64!", &recipe, Config::default()).unwrap_err();
+/// assert!(failure.step == 1);
+/// ```
+/// # Errors
+/// Returns which step of the recipe failed, and why.
+pub fn perform_recipe(
+    text: &str,
+    recipe: &recipe::Recipe,
+    config: Config,
+) -> Result<DecoderResult, recipe::RecipeFailure> {
+    config::set_global_config(config);
+    recipe.run(text)
+}
+
 /// Checks if the given input is plaintext or not
 /// Used at the start of the program to not waste CPU cycles
+/// Steganography hides a message inside text that looks like plaintext,
+/// so if we can pull a hidden message out we carry on searching instead.
 fn check_if_input_text_is_plaintext(text: &str) -> CheckResult {
     let athena_checker = Checker::<Athena>::new();
+    if contains_zero_width_message(text) || contains_whitespace_message(text) {
+        debug!("The input text has a hidden message, so we will not return early");
+        return CheckResult::new(&athena_checker);
+    }
     athena_checker.check(text)
 }
 
@@ -140,6 +255,22 @@ impl Default for DecoderResult {
 
 /// Lets us create a new decoderResult with given text
 impl DecoderResult {
+    /// The plaintext as bytes, to write it to a file.
+    /// If the last decoder decoded bytes (like base64) these are exactly those bytes,
+    /// so binary files come out as they went in. Otherwise the text is turned back
+    /// into bytes, which keeps a binary file a decoder held one char per byte.
+    pub fn plaintext_bytes(&self) -> Vec<u8> {
+        self.path
+            .last()
+            .and_then(|result| result.unencrypted_bytes.clone())
+            .unwrap_or_else(|| checkers::file_type_checker::text_to_bytes(&self.text[0]))
+    }
+
+    /// What the checker found in the plaintext, like a CTF flag, if it found a part of it
+    pub fn matched(&self) -> Option<&str> {
+        self.path.last()?.matched.as_deref()
+    }
+
     /// It's only used in tests so it thinks its dead code
     fn _new(text: &str) -> Self {
         DecoderResult {
@@ -151,13 +282,17 @@ impl DecoderResult {
 
 #[cfg(test)]
 mod tests {
-    use super::perform_cracking;
+    use super::{perform_batch_cracking, perform_cracking, DecoderResult};
     use crate::config::Config;
+    use crate::encoders::EncoderChain;
+    use crate::error::Error;
+    use crate::recipe::Recipe;
+    use base64::{engine::general_purpose, Engine as _};
 
     #[test]
     fn test_perform_cracking_returns() {
         let config = Config::default();
-        perform_cracking("SGVscCBJIG5lZWQgc29tZWJvZHkh", config);
+        let _ = perform_cracking("SGVscCBJIG5lZWQgc29tZWJvZHkh", config);
     }
 
     #[test]
@@ -168,21 +303,62 @@ mod tests {
         // assert!(result.unwrap() == "CANARY: hello")
         let config = Config::default();
         let result = perform_cracking("b2xsZWg=", config);
-        assert!(result.is_some());
+        assert!(result.is_ok());
         assert!(result.unwrap().text[0] == "hello");
     }
+    #[test]
+    fn test_perform_batch_cracking_keeps_order() {
+        let config = Config::default();
+        let texts = vec![
+            "aGVsbG8gdGhlcmUgZ2VuZXJhbA==".to_string(),
+            String::new(),
+            "192.168.0.1".to_string(),
+        ];
+        let results = perform_batch_cracking(&texts, config).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].as_ref().unwrap().text[0] == "hello there general");
+        assert!(matches!(results[1], Err(Error::NotFound)));
+        assert!(results[2].as_ref().unwrap().text[0] == "192.168.0.1");
+    }
+
+    #[test]
+    fn test_perform_batch_cracking_outlives_finished_searches() {
+        // The first text is decoded straight away, while the second searches until
+        // it runs out of time, long after the first search's timer was due
+        let texts = vec![
+            "aHR0cHM6Ly93d3cuZ29vZ2xlLmNvbQ==".to_string(),
+            "qzvk pwxo rnsf tbyd mhlc gaej".to_string(),
+        ];
+        let results = perform_batch_cracking(&texts, Config::default()).unwrap();
+        assert_eq!(
+            results[0].as_ref().unwrap().text[0],
+            "https://www.google.com"
+        );
+        assert!(results[1].is_err());
+    }
+
     #[test]
     fn test_perform_cracking_returns_failure() {
         let config = Config::default();
         let result = perform_cracking("", config);
-        assert!(result.is_none());
+        assert!(matches!(result, Err(Error::NotFound)));
+    }
+
+    #[test]
+    fn test_perform_cracking_rejects_invalid_regex() {
+        let config = Config {
+            regex: Some("(unclosed".to_string()),
+            ..Config::default()
+        };
+        let result = perform_cracking("b2xsZWg=", config);
+        assert!(matches!(result, Err(Error::InvalidRegex { .. })));
     }
 
     #[test]
     fn test_perform_cracking_returns_successful_base64_reverse() {
         let config = Config::default();
         let result = perform_cracking("aGVsbG8gdGhlcmUgZ2VuZXJhbA==", config);
-        assert!(result.is_some());
+        assert!(result.is_ok());
         assert!(result.unwrap().text[0] == "hello there general")
     }
 
@@ -206,6 +382,47 @@ mod tests {
         assert!(result.unwrap().text[0] == "Rotate me 13 places!");
     }
 
+    #[test]
+    fn test_perform_cracking_extracts_the_flag() {
+        let encoded = general_purpose::STANDARD
+            .encode("Well done! Your flag is picoCTF{b4s3_64_1s_n0t_3ncrypt10n} :)");
+        let result = perform_cracking(&encoded, Config::default()).unwrap();
+        assert_eq!(result.matched(), Some("picoCTF{b4s3_64_1s_n0t_3ncrypt10n}"));
+        assert_eq!(result.path.last().unwrap().checker_name, "Flag Checker");
+    }
+
+    #[test]
+    fn test_printed_recipe_replays_a_caesar_step() {
+        // Caesar isn't the last step, so the key has to come from the shift the search
+        // followed. It follows the first shift caesar tries, which undoes a key of 25.
+        let encoded = "caesar:25"
+            .parse::<EncoderChain>()
+            .unwrap()
+            .encode("aHR0cHM6Ly93d3cuZ29vZ2xlLmNvbQ==")
+            .unwrap();
+        let result = perform_cracking(&encoded, Config::default()).unwrap();
+        assert_eq!(result.text[0], "https://www.google.com");
+        let printed = Recipe::from_path(&result.path).to_string();
+        assert_eq!(printed, "caesar:25,base64");
+        let replayed = printed.parse::<Recipe>().unwrap().run(&encoded).unwrap();
+        assert_eq!(replayed.text, result.text);
+    }
+
+    #[test]
+    fn test_binary_files_keep_their_bytes() {
+        // `c3 a2 c3 a3` is valid UTF-8 (âã), so the text alone can't say
+        // whether these were two bytes or four
+        let mut pdf = b"%PDF-1.7\n%".to_vec();
+        pdf.extend([0xc3, 0xa2, 0xc3, 0xa3, 0x0a, 0xe2, 0xe3, 0xcf, 0xd3]);
+        let encoded = general_purpose::STANDARD.encode(&pdf);
+        let result = perform_cracking(&encoded, Config::default()).unwrap();
+        assert_eq!(result.plaintext_bytes(), pdf);
+
+        let text = "h\u{e9}llo".to_string();
+        let plaintext = DecoderResult::_new(&text);
+        assert_eq!(plaintext.plaintext_bytes(), text.as_bytes());
+    }
+
     #[test]
     fn test_successfully_inputted_plaintext() {
         let config = Config::default();
@@ -1,17 +1,100 @@
-use ares::cli::parse_cli_args;
-use ares::cli_pretty_printing::program_exiting_successful_decoding;
-use ares::perform_cracking;
+use std::process::ExitCode;
 
-fn main() {
+use ares::cli::{parse_cli_args, write_decoded_output, BatchInput, CliInput};
+use ares::cli_pretty_printing::{
+    batch_result, decoding_explained, effective_config, encoded_text, explain_component,
+    list_components, program_exiting_successful_decoding, program_exiting_with_error,
+    program_exiting_wrote_output_file,
+};
+use ares::config::{get_config, Config};
+use ares::error::{Error, EXIT_SUCCESS};
+use ares::registry::{self, ComponentKind};
+use ares::{perform_batch_cracking, perform_cracking, perform_recipe, DecoderResult};
+
+/// Searches for the plaintext, or lets the user explore the search tree with `--tui`
+fn crack(text: &str, config: Config) -> Result<DecoderResult, Error> {
+    #[cfg(feature = "tui")]
+    if config.tui {
+        return ares::tui::explore(text, config)
+            .map_err(Error::Tui)?
+            .ok_or(Error::NotFound);
+    }
+    perform_cracking(text, config)
+}
+
+/// Cracks every input of a batch and prints a record for each one.
+/// Each record says if its input failed, and we exit with not found if any did.
+fn crack_batch(inputs: &[BatchInput], config: Config) -> Result<(), Error> {
+    let texts: Vec<String> = inputs.iter().map(|input| input.text.clone()).collect();
+    let results = perform_batch_cracking(&texts, config)?;
+    for (input, result) in inputs.iter().zip(&results) {
+        batch_result(&input.source, &input.text, result.as_ref());
+    }
+    if results.iter().all(Result::is_ok) {
+        Ok(())
+    } else {
+        Err(Error::NotFound)
+    }
+}
+
+/// Does what the CLI was asked to do
+fn run() -> Result<(), Error> {
     // Turn CLI arguments into a library object
-    let (text, config) = parse_cli_args();
-    let result = perform_cracking(&text, config);
-    match result {
-        // TODO: As result have array of CrackResult used,
-        // we can print in better way with more info
-        Some(result) => {
-            program_exiting_successful_decoding(result);
+    let (input, config) = parse_cli_args()?;
+    let from_recipe = matches!(input, CliInput::Recipe { .. });
+    let result = match input {
+        CliInput::Single(text) => crack(&text, config)?,
+        CliInput::Recipe { recipe, text } => {
+            perform_recipe(&text, &recipe, config).map_err(Error::RecipeFailed)?
+        }
+        CliInput::Batch(inputs) => return crack_batch(&inputs, config),
+        CliInput::Encode { chain, text } => {
+            let encoded = chain.encode(&text).map_err(Error::CouldNotEncode)?;
+            encoded_text(&encoded);
+            return Ok(());
+        }
+        CliInput::List { kind, json } => {
+            let components = match kind {
+                ComponentKind::Decoder => registry::decoder_info(),
+                ComponentKind::Checker => registry::checker_info(),
+            };
+            list_components(&components, json);
+            return Ok(());
+        }
+        CliInput::PrintConfig { files, settings } => {
+            effective_config(&files, &settings);
+            return Ok(());
+        }
+        CliInput::Explain(name) => {
+            let component = registry::find(&name).ok_or(Error::UnknownComponent(name))?;
+            explain_component(&component);
+            return Ok(());
+        }
+    };
+    // TODO: As result have array of CrackResult used,
+    // we can print in better way with more info
+    decoding_explained(&result, from_recipe);
+    match &get_config().output_file {
+        Some(file_path) => {
+            let bytes_written = write_decoded_output(file_path, &result).map_err(|error| {
+                Error::CouldNotWriteOutput {
+                    path: file_path.clone(),
+                    error,
+                }
+            })?;
+            program_exiting_wrote_output_file(file_path, bytes_written);
+        }
+        None => program_exiting_successful_decoding(result),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::from(EXIT_SUCCESS),
+        Err(error) => {
+            program_exiting_with_error(&error);
+            ExitCode::from(error.exit_code())
         }
-        None => ares::cli_pretty_printing::failed_to_decode(),
     }
 }
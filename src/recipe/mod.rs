@@ -0,0 +1,248 @@
+//! Recipes replay a chain of decoders we already know, like `base64,caesar:13,hex`,
+//! so new inputs from the same source are decoded without searching.
+//! Names are the same as `ares encode` uses (or the decoder's own name)
+//! and keys go after a colon. Successful searches are printed as recipes too.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::checkers::athena::Athena;
+use crate::checkers::checker_type::{Check, Checker};
+use crate::checkers::CheckerTypes;
+use crate::decoders::crack_results::CrackResult;
+use crate::decoders::interface::Decoder;
+use crate::encoders::{get_encoder, ENCODERS};
+use crate::registry;
+use crate::DecoderResult;
+
+/// One decoder in a recipe, with its key if it has one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipeStep {
+    /// The name of the decoder, like `Caesar Cipher`
+    pub decoder: String,
+    /// The key to decode with, like `13` for caesar
+    pub key: Option<String>,
+}
+
+/// Decoders to run one after the other, written like `base64,caesar:13,hex`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recipe {
+    /// The decoders, in the order they run
+    pub steps: Vec<RecipeStep>,
+}
+
+/// Why a recipe couldn't decode the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipeFailure {
+    /// The step which failed, counting from 1
+    pub step: usize,
+    /// The decoder at that step
+    pub decoder: String,
+    /// The text that step was given
+    pub text: String,
+    /// What went wrong
+    pub reason: String,
+}
+
+impl fmt::Display for RecipeFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {} ({}) {}", self.step, self.decoder, self.reason)
+    }
+}
+
+impl FromStr for Recipe {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let steps = input
+            .split(',')
+            .map(|step| {
+                let (name, key) = match step.split_once(':') {
+                    Some((name, key)) => (name, Some(key.trim().to_string())),
+                    None => (step, None),
+                };
+                // Encoder names are the short ones, like `hex` for Hexadecimal.
+                // Some imply a key, like `rot13` which is caesar with a key of 13.
+                let (decoder, key) = match get_encoder(name) {
+                    Some(encoder) => (
+                        Some(encoder.decoder.to_string()),
+                        key.or_else(|| encoder.decoder_key.map(str::to_string)),
+                    ),
+                    None => (
+                        registry::decoder(name).map(|decoder| decoder.get_name().to_string()),
+                        key,
+                    ),
+                };
+                let decoder = decoder
+                    .ok_or_else(|| format!("there's no decoder called {:?}", name.trim()))?;
+                Ok(RecipeStep { decoder, key })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Recipe { steps })
+    }
+}
+
+impl fmt::Display for Recipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps: Vec<String> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let name = recipe_name(&step.decoder);
+                match &step.key {
+                    Some(key) => format!("{name}:{key}"),
+                    None => name,
+                }
+            })
+            .collect();
+        write!(f, "{}", steps.join(","))
+    }
+}
+
+/// The short name we write a decoder with in recipes, the same as its encoder's
+pub(crate) fn recipe_name(decoder: &str) -> String {
+    ENCODERS
+        .iter()
+        .find(|encoder| encoder.decoder == decoder)
+        .map_or_else(
+            || normalise_name(decoder),
+            |encoder| encoder.name.to_string(),
+        )
+}
+
+/// Lowercases the name and turns spaces and dashes into underscores
+fn normalise_name(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+impl Recipe {
+    /// The recipe for a path the searcher found, so it can be replayed on other inputs.
+    /// The default decoder (used when the input is already plaintext) isn't a step.
+    pub fn from_path(path: &[CrackResult]) -> Recipe {
+        let default_decoder = Decoder::default().name;
+        Recipe {
+            steps: path
+                .iter()
+                .filter(|result| result.decoder != default_decoder)
+                .map(|result| RecipeStep {
+                    decoder: result.decoder.to_string(),
+                    key: result.key.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Runs each decoder on the output of the one before it.
+    /// Decoders given a key only decode with that key. Without one they try
+    /// every key as usual, so they have to find the plaintext or give back one text.
+    /// # Errors
+    /// Returns which step failed and why if a decoder can't decode its input.
+    pub fn run(&self, text: &str) -> Result<DecoderResult, RecipeFailure> {
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        let mut text = text.to_string();
+        let mut path = Vec::with_capacity(self.steps.len());
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let failure = |reason: String| RecipeFailure {
+                step: index + 1,
+                decoder: step.decoder.clone(),
+                text: text.clone(),
+                reason,
+            };
+            let decoder = registry::decoder(&step.decoder)
+                .ok_or_else(|| failure("isn't a decoder we have".to_string()))?;
+            let result = match &step.key {
+                Some(key) => decoder
+                    .crack_with_key(&text, key, &checker)
+                    .ok_or_else(|| failure(format!("can't decode with the key {key:?}")))?,
+                None => decoder.crack(&text, &checker),
+            };
+            let decoded = match result.unencrypted_text.as_deref() {
+                Some([decoded]) => decoded.clone(),
+                Some(texts) if texts.len() > 1 => {
+                    return Err(failure(format!(
+                        "gave {} possible texts, add a key to say which one",
+                        texts.len()
+                    )))
+                }
+                _ => return Err(failure("couldn't decode the text".to_string())),
+            };
+            text = decoded;
+            path.push(result);
+        }
+
+        Ok(DecoderResult {
+            text: vec![text],
+            path,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Recipe;
+    use crate::encoders::{EncoderChain, ENCODERS};
+
+    #[test]
+    fn test_parse_and_display() {
+        let recipe: Recipe = "Base64, caesar:13,Hexadecimal".parse().unwrap();
+        assert_eq!(recipe.steps.len(), 3);
+        assert_eq!(recipe.steps[1].decoder, "Caesar Cipher");
+        assert_eq!(recipe.steps[1].key.as_deref(), Some("13"));
+        assert_eq!(recipe.to_string(), "base64,caesar:13,hex");
+        assert!("base64,nope".parse::<Recipe>().is_err());
+    }
+
+    #[test]
+    fn test_run_replays_an_encoding_chain() {
+        let chain: EncoderChain = "hex,caesar:3,railfence:4:1,base64".parse().unwrap();
+        let encoded = chain.encode("hello there general kenobi").unwrap();
+        let recipe: Recipe = "base64,railfence:4:1,caesar:3,hex".parse().unwrap();
+        let result = recipe.run(&encoded).unwrap();
+        assert_eq!(result.text[0], "hello there general kenobi");
+        assert_eq!(result.path.len(), 4);
+        assert_eq!(Recipe::from_path(&result.path), recipe);
+    }
+
+    #[test]
+    fn test_encoders_with_a_fixed_key_replay() {
+        let text = "hello there general kenobi";
+        let keyed = ENCODERS
+            .iter()
+            .filter(|encoder| encoder.decoder_key.is_some());
+        for encoder in keyed {
+            let chain: EncoderChain = format!("{},base64,hex", encoder.name).parse().unwrap();
+            let encoded = chain.encode(text).unwrap();
+            let recipe: Recipe = format!("hex,base64,{}", encoder.name).parse().unwrap();
+            assert_eq!(recipe.steps[2].key.as_deref(), encoder.decoder_key);
+            assert_eq!(
+                recipe.run(&encoded).unwrap().text[0],
+                text,
+                "{}",
+                encoder.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_reports_the_failed_step() {
+        let recipe: Recipe = "base64,hex".parse().unwrap();
+        // "aGVsbG8=" is base64 for "hello", which isn't hex
+        let failure = recipe.run("aGVsbG8=").unwrap_err();
+        assert_eq!(failure.step, 2);
+        assert_eq!(failure.decoder, "Hexadecimal");
+        assert_eq!(failure.text, "hello");
+        assert_eq!(
+            failure.to_string(),
+            "step 2 (Hexadecimal) couldn't decode the text"
+        );
+    }
+
+    #[test]
+    fn test_run_rejects_bad_keys() {
+        let recipe: Recipe = "base64:3".parse().unwrap();
+        let failure = recipe.run("aGVsbG8=").unwrap_err();
+        assert_eq!(failure.step, 1);
+        assert!(failure.reason.contains("key"));
+    }
+}
@@ -0,0 +1,289 @@
+//! The registry is every decoder and checker Ares has, in one place.
+//! Decoders register themselves with `register_decoders!` in the decoders module,
+//! and the registry builds them once and shares them. The filtration system, recipes,
+//! `ares list` and `ares explain` all look decoders up here, so they all see the same set.
+
+use std::fmt;
+
+use once_cell::sync::Lazy;
+
+use crate::checkers::{
+    athena::Athena,
+    checker_expression::NamedChecker,
+    checker_type::{Check, Checker},
+    crib_checker::CribChecker,
+    english::EnglishChecker,
+    file_type_checker::FileTypeChecker,
+    flag_checker::FlagChecker,
+    language_checker::LanguageChecker,
+    lemmeknow_checker::LemmeKnow,
+    quadgram_checker::QuadgramChecker,
+    regex_checker::RegexChecker,
+    structured_data_checker::StructuredDataChecker,
+};
+use crate::decoders::interface::Crack;
+use crate::decoders::registered_decoders;
+use crate::recipe::recipe_name;
+
+/// Whether something in the registry is a decoder or a checker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentKind {
+    /// Decoders turn one text into another
+    Decoder,
+    /// Checkers decide if a text is the plaintext
+    Checker,
+}
+
+impl fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentKind::Decoder => write!(f, "decoder"),
+            ComponentKind::Checker => write!(f, "checker"),
+        }
+    }
+}
+
+/// What a decoder or checker is, for `ares list` and `ares explain`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentInfo {
+    /// Whether it's a decoder or a checker
+    pub kind: ComponentKind,
+    /// The English name, like `Caesar Cipher`
+    pub name: &'static str,
+    /// The name to use on the command line, in `--recipe` for decoders
+    /// and `--checkers` for checkers, like `caesar`. Athena runs by default so it has none
+    pub short_name: Option<String>,
+    /// What it does
+    pub description: &'static str,
+    /// Where to read more about it
+    pub link: &'static str,
+    /// Its categories, like `base` or `cipher`
+    pub tags: Vec<&'static str>,
+    /// How often it's used, from 0 to 1
+    pub popularity: f32,
+}
+
+impl ComponentInfo {
+    /// The info as JSON, for `ares list --json`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "kind": self.kind.to_string(),
+            "name": self.name,
+            "short_name": self.short_name,
+            "description": self.description,
+            "link": self.link,
+            "tags": self.tags,
+            "popularity": self.popularity,
+        })
+    }
+}
+
+/// A decoder in the registry
+pub type RegisteredDecoder = dyn Crack + Send + Sync;
+
+/// Every decoder registered in the decoders module, built the first time it's used
+static DECODERS: Lazy<Vec<Box<RegisteredDecoder>>> = Lazy::new(registered_decoders);
+
+/// Every decoder, in the order the searcher runs them
+pub fn decoders() -> &'static [Box<RegisteredDecoder>] {
+    &DECODERS
+}
+
+/// Finds a decoder by its name or the name used in recipes, ignoring case,
+/// spaces, dashes and underscores. So `Base58 Bitcoin` and `base58_bitcoin` both work.
+pub fn decoder(name: &str) -> Option<&'static RegisteredDecoder> {
+    let name = normalise_name(name);
+    decoders().iter().map(Box::as_ref).find(|decoder| {
+        normalise_name(decoder.get_name()) == name
+            || normalise_name(&recipe_name(decoder.get_name())) == name
+    })
+}
+
+/// Every decoder with this tag, like `base` or `cipher`, ignoring case
+pub fn decoders_with_tag(tag: &str) -> Vec<&'static RegisteredDecoder> {
+    decoders()
+        .iter()
+        .map(Box::as_ref)
+        .filter(|decoder| {
+            decoder
+                .get_tags()
+                .iter()
+                .any(|decoder_tag| decoder_tag.eq_ignore_ascii_case(tag.trim()))
+        })
+        .collect()
+}
+
+/// What each decoder is, in the order the searcher runs them
+pub fn decoder_info() -> Vec<ComponentInfo> {
+    decoders()
+        .iter()
+        .map(|decoder| ComponentInfo {
+            kind: ComponentKind::Decoder,
+            name: decoder.get_name(),
+            short_name: Some(recipe_name(decoder.get_name())),
+            description: decoder.get_description(),
+            link: decoder.get_link(),
+            tags: decoder.get_tags().clone(),
+            popularity: decoder.get_popularity(),
+        })
+        .collect()
+}
+
+/// What each checker is. Athena runs the others, the rest can be used in `--checkers`
+pub fn checker_info() -> Vec<ComponentInfo> {
+    vec![
+        describe_checker(None, Checker::<Athena>::new()),
+        describe_checker(
+            Some(NamedChecker::Regex.name()),
+            Checker::<RegexChecker>::new(),
+        ),
+        describe_checker(
+            Some(NamedChecker::Crib.name()),
+            Checker::<CribChecker>::new(),
+        ),
+        describe_checker(
+            Some(NamedChecker::Flag.name()),
+            Checker::<FlagChecker>::new(),
+        ),
+        describe_checker(
+            Some(NamedChecker::FileType.name()),
+            Checker::<FileTypeChecker>::new(),
+        ),
+        describe_checker(
+            Some(NamedChecker::LemmeKnow.name()),
+            Checker::<LemmeKnow>::new(),
+        ),
+        describe_checker(
+            Some(NamedChecker::StructuredData.name()),
+            Checker::<StructuredDataChecker>::new(),
+        ),
+        describe_checker(
+            Some(NamedChecker::English.name()),
+            Checker::<EnglishChecker>::new(),
+        ),
+        describe_checker(
+            Some(NamedChecker::Language.name()),
+            Checker::<LanguageChecker>::new(),
+        ),
+        describe_checker(
+            Some(NamedChecker::Quadgram.name()),
+            Checker::<QuadgramChecker>::new(),
+        ),
+    ]
+}
+
+/// Takes the info we show from a checker
+fn describe_checker<Type>(short_name: Option<&str>, checker: Checker<Type>) -> ComponentInfo {
+    ComponentInfo {
+        kind: ComponentKind::Checker,
+        name: checker.name,
+        short_name: short_name.map(str::to_string),
+        description: checker.description,
+        link: checker.link,
+        tags: checker.tags,
+        popularity: checker.popularity,
+    }
+}
+
+/// Finds a decoder or checker by its name or short name, ignoring case,
+/// spaces, dashes and underscores. So `caesar`, `Caesar Cipher` and `file-type` all work,
+/// and checkers can leave off the word checker, like `athena`.
+pub fn find(name: &str) -> Option<ComponentInfo> {
+    let name = normalise_name(name);
+    decoder_info()
+        .into_iter()
+        .chain(checker_info())
+        .find(|info| {
+            let full_name = normalise_name(info.name);
+            full_name == name
+                || (info.kind == ComponentKind::Checker
+                    && full_name.strip_suffix("checker") == Some(name.as_str()))
+                || info
+                    .short_name
+                    .as_ref()
+                    .is_some_and(|short_name| normalise_name(short_name) == name)
+        })
+}
+
+/// Lowercases the name and drops spaces, dashes and underscores
+fn normalise_name(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-', '_'], "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        checker_info, decoder, decoder_info, decoders, decoders_with_tag, find, ComponentKind,
+    };
+
+    #[test]
+    fn test_every_decoder_is_described() {
+        let info = decoder_info();
+        assert_eq!(info.len(), decoders().len());
+        assert!(info
+            .iter()
+            .all(|info| !info.description.is_empty() && info.short_name.is_some()));
+    }
+
+    #[test]
+    fn test_find_by_either_name() {
+        let caesar = find("caesar").unwrap();
+        assert_eq!(caesar.name, "Caesar Cipher");
+        assert_eq!(caesar.kind, ComponentKind::Decoder);
+        assert_eq!(find("Caesar cipher"), Some(caesar));
+        assert_eq!(find("hex").unwrap().name, "Hexadecimal");
+        assert_eq!(find("file-type").unwrap().kind, ComponentKind::Checker);
+        assert_eq!(find("athena").unwrap().name, "Athena Checker");
+        assert!(find("nope").is_none());
+    }
+
+    #[test]
+    fn test_short_names_are_unique() {
+        let mut names: Vec<String> = decoder_info()
+            .into_iter()
+            .chain(checker_info())
+            .filter_map(|info| info.short_name)
+            .collect();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn test_decoders_are_built_once() {
+        assert!(std::ptr::eq(decoders(), decoders()));
+    }
+
+    #[test]
+    fn test_decoder_by_name() {
+        assert_eq!(
+            decoder("base58_bitcoin").unwrap().get_name(),
+            "Base58 Bitcoin"
+        );
+        assert_eq!(
+            decoder("CAESAR CIPHER").unwrap().get_name(),
+            "Caesar Cipher"
+        );
+        assert_eq!(decoder("hex").unwrap().get_name(), "Hexadecimal");
+        assert!(decoder("nope").is_none());
+    }
+
+    #[test]
+    fn test_decoders_with_tag() {
+        let base58: Vec<&str> = decoders_with_tag("Base58")
+            .iter()
+            .map(|decoder| decoder.get_name())
+            .collect();
+        assert_eq!(
+            base58,
+            [
+                "Base58 Bitcoin",
+                "Base58 Monero",
+                "Base58 Ripple",
+                "Base58 Flickr"
+            ]
+        );
+        assert!(decoders_with_tag("nope").is_empty());
+    }
+}
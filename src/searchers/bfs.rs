@@ -38,44 +38,36 @@ pub fn bfs(input: String, result_sender: Sender<Option<DecoderResult>>, stop: Ar
                 MyResults::Break(res) => {
                     let mut decoders_used = current_string.path;
                     let text = res.unencrypted_text.clone().unwrap_or_default();
-                    decoders_used.push(res);
+                    decoders_used.push(*res);
                     let result_text = DecoderResult {
                         text,
                         path: decoders_used,
                     };
 
                     decoded_how_many_times(curr_depth);
-                    result_sender
-                        .send(Some(result_text))
-                        .expect("Should succesfully send the result");
+                    // The search may have timed out already, so nobody is listening
+                    result_sender.send(Some(result_text)).ok();
 
                     // stop further iterations
                     stop.store(true, std::sync::atomic::Ordering::Relaxed);
                     None // short-circuits the iterator
                 }
                 MyResults::Continue(results_vec) => {
-                    new_strings.extend(results_vec.into_iter().flat_map(|mut r| {
-                        let mut decoders_used = current_string.path.clone();
-                        // text is a vector of strings
-                        let mut text = r.unencrypted_text.take().unwrap_or_default();
-
-                        text.retain(|s| {
-                            !check_if_string_cant_be_decoded(s) && seen_strings.insert(s.clone())
+                    new_strings.extend(results_vec.into_iter().filter_map(|result| {
+                        let mut candidates = result.into_candidates();
+                        candidates.retain(|(text, _)| {
+                            !check_if_string_cant_be_decoded(text)
+                                && seen_strings.insert(text.clone())
                         });
-
-                        if text.is_empty() {
-                            return None;
-                        }
-
-                        decoders_used.push(r);
+                        // Decoders like caesar give a text for every key. Only the first is
+                        // decoded further, as following them all makes each level many times
+                        // bigger. The path gets that text with its own key, so it can be replayed.
+                        let (text, candidate) = candidates.into_iter().next()?;
+                        let mut decoders_used = current_string.path.clone();
+                        decoders_used.push(candidate);
                         Some(DecoderResult {
-                            // and this is a vector of strings
-                            // TODO we should probably loop through all `text` and create Text structs for each one
-                            // and append those structs
-                            // I think we should keep text as a single string
-                            // and just create more of them....
-                            text,
-                            path: decoders_used.to_vec(),
+                            text: vec![text],
+                            path: decoders_used,
                         })
                     }));
                     Some(()) // indicate we want to continue processing
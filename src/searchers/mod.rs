@@ -12,8 +12,10 @@ use log::debug;
 
 use crate::checkers::athena::Athena;
 use crate::checkers::checker_type::{Check, Checker};
+use crate::checkers::human_checker::{self, SearchState};
 use crate::checkers::CheckerTypes;
 use crate::config::get_config;
+use crate::error::Error;
 use crate::filtration_system::{filter_and_get_decoders, MyResults};
 use crate::{timer, DecoderResult};
 /// This module provides access to the breadth first search
@@ -36,22 +38,38 @@ mod bfs;
 /// We can return an Option? An Enum? And then match on that
 /// So if we return CrackSuccess we return
 /// Else if we return an array, we add it to the children and go again.
-pub fn search_for_plaintext(input: String) -> Option<DecoderResult> {
+/// # Errors
+/// Returns `Error::Timeout` if we run out of time, or `Error::NotFound`
+/// if there's nothing left to decode or the human checker asks us to stop.
+pub fn search_for_plaintext(input: String) -> Result<DecoderResult, Error> {
     let timeout = get_config().timeout;
-    let timer = timer::start(timeout);
+    // What the human told this search, batches run several searches at once
+    let search = Arc::new(SearchState::default());
+    // For stopping the thread, and the timer with it
+    let stop = Arc::new(AtomicBool::new(false));
+    let timer = timer::start(timeout, search.paused(), stop.clone());
 
     let (result_sender, result_recv) = bounded::<Option<DecoderResult>>(1);
-    // For stopping the thread
-    let stop = Arc::new(AtomicBool::new(false));
     let s = stop.clone();
+    let bfs_search = search.clone();
     // Change this to select which search algorithm we want to use.
-    let handle = thread::spawn(move || bfs::bfs(input, result_sender, s));
+    let handle = thread::spawn(move || {
+        human_checker::set_current_search(bfs_search);
+        bfs::bfs(input, result_sender, s)
+    });
 
     loop {
         if let Ok(res) = result_recv.try_recv() {
             debug!("Found exit result: {:?}", res);
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
             handle.join().unwrap();
-            return res;
+            return res.ok_or(Error::NotFound);
+        }
+
+        // The human checker can ask us to stop
+        if search.quit_requested() {
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+            return Err(Error::NotFound);
         }
 
         if timer.try_recv().is_ok() {
@@ -59,7 +77,7 @@ pub fn search_for_plaintext(input: String) -> Option<DecoderResult> {
             debug!("Ares has failed to decode");
             // this would wait for whole iteration to finish!
             // handle.join().unwrap();
-            return None;
+            return Err(Error::Timeout { seconds: timeout });
         }
     }
 }
@@ -68,6 +86,9 @@ pub fn search_for_plaintext(input: String) -> Option<DecoderResult> {
 /// and calling `.run` which in turn loops through them and calls
 /// `.crack()`.
 fn perform_decoding(text: &DecoderResult) -> MyResults {
+    if let Some(search) = human_checker::current_search() {
+        search.set_path(text.path.iter().map(|result| result.decoder).collect());
+    }
     let decoders = filter_and_get_decoders(text);
     let athena_checker = Checker::<Athena>::new();
     let checker = CheckerTypes::CheckAthena(athena_checker);
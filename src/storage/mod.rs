@@ -3,6 +3,9 @@ use include_dir::Dir;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
 
 /// Tells Rust to load the dictionaries into the binary
 /// at compile time. Which means that we do not waste
@@ -25,6 +28,141 @@ pub static DICTIONARIES: Lazy<HashMap<&str, HashSet<&str>>> = Lazy::new(|| {
     entries
 });
 
+/// Log probabilities of English quadgrams (four letter sequences), used to score
+/// how English-like a string is even when it has no spaces.
+pub struct QuadgramTable {
+    /// log10 probability of each upper case quadgram we have seen
+    pub log_probabilities: HashMap<&'static str, f64>,
+    /// log10 probability we give to quadgrams we have never seen
+    pub floor: f64,
+}
+
+/// Loads the English quadgram counts into the binary at compile time.
+/// Each line of the file is `QUADGRAM count`, counted from English prose
+/// with everything except the letters A-Z removed.
+pub static ENGLISH_QUADGRAMS: Lazy<QuadgramTable> = Lazy::new(|| {
+    /// The quadgram counts, most common first
+    static QUADGRAM_COUNTS: &str = include_str!("ngrams/english_quadgrams.txt");
+    let counts: Vec<(&str, f64)> = QUADGRAM_COUNTS
+        .lines()
+        .filter_map(|line| {
+            let (quadgram, count) = line.split_once(' ')?;
+            Some((quadgram, count.parse().ok()?))
+        })
+        .collect();
+    let total: f64 = counts.iter().map(|(_, count)| count).sum();
+
+    QuadgramTable {
+        log_probabilities: counts
+            .into_iter()
+            .map(|(quadgram, count)| (quadgram, (count / total).log10()))
+            .collect(),
+        floor: (0.01 / total).log10(),
+    }
+});
+
+/// Loads the user's own wordlists at runtime, so they can add words without recompiling.
+/// Each path can be a file, or a directory in which case every file inside it is loaded.
+/// Words are normalised the same way the English checker normalises its input.
+/// Returns the words with the file they came from, so we can say which list matched.
+/// The lists stay in the order they were given, so the first one with a word gets the credit.
+pub fn load_wordlists(paths: &[String]) -> io::Result<Vec<(String, HashSet<String>)>> {
+    let mut wordlists = Vec::new();
+    for path in paths {
+        let path = Path::new(path);
+        if path.is_dir() {
+            let mut files: Vec<_> = fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<_>>()?;
+            // Sort so the lists are always loaded in the same order
+            files.sort();
+            for file in files.iter().filter(|file| file.is_file()) {
+                wordlists.push((file.display().to_string(), read_wordlist(file)?));
+            }
+        } else {
+            wordlists.push((path.display().to_string(), read_wordlist(path)?));
+        }
+    }
+    Ok(wordlists)
+}
+
+/// Reads a single wordlist, one or more words per line
+fn read_wordlist(path: &Path) -> io::Result<HashSet<String>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content
+        .split_ascii_whitespace()
+        .map(normalise_string)
+        .filter(|word| !word.is_empty())
+        .collect())
+}
+
+/// Strings look funny, they might have commas, be uppercase etc
+/// This normalises the string so English checker can work on it
+/// In particular it:
+/// Removes punctuation from the string
+/// Lowercases the string
+pub(crate) fn normalise_string(input: &str) -> String {
+    // The replace function supports patterns https://doc.rust-lang.org/std/str/pattern/trait.Pattern.html#impl-Pattern%3C%27a%3E-3
+    // TODO add more punctuation
+    input
+        .to_ascii_lowercase()
+        .chars()
+        .filter(|x| !x.is_ascii_punctuation())
+        .collect()
+}
+
+/// The non-English languages we can detect, as (ISO 639-1 code, name)
+pub const SUPPORTED_LANGUAGES: [(&str, &str); 5] = [
+    ("fr", "French"),
+    ("de", "German"),
+    ("es", "Spanish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+];
+
+/// Everything we know about a language to decide if text is written in it
+pub struct LanguageProfile {
+    /// The name of the language, like "French"
+    pub name: &'static str,
+    /// The most common words of the language, lower case
+    pub words: HashSet<&'static str>,
+    /// How often each letter appears in normal text, as a percentage
+    pub letter_frequencies: HashMap<char, f64>,
+}
+
+/// Loads the word lists and letter frequencies for every supported language,
+/// keyed by the ISO 639-1 code. Each language has a folder in `languages/` with
+/// `words.txt` (whitespace separated words) and `letters.txt` (`letter percentage` per line).
+pub static LANGUAGES: Lazy<HashMap<&str, LanguageProfile>> = Lazy::new(|| {
+    /// The directory where our language profiles are stored.
+    static LANGUAGES_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/storage/languages");
+    let read = |code: &str, file: &str| -> &'static str {
+        LANGUAGES_DIR
+            .get_file(format!("{code}/{file}"))
+            .and_then(|entry| entry.contents_utf8())
+            .expect("Every supported language needs a UTF-8 words.txt and letters.txt")
+    };
+
+    SUPPORTED_LANGUAGES
+        .iter()
+        .map(|(code, name)| {
+            let letter_frequencies = read(code, "letters.txt")
+                .lines()
+                .filter_map(|line| {
+                    let (letter, frequency) = line.split_once(' ')?;
+                    Some((letter.chars().next()?, frequency.parse().ok()?))
+                })
+                .collect();
+            let profile = LanguageProfile {
+                name,
+                words: read(code, "words.txt").split_whitespace().collect(),
+                letter_frequencies,
+            };
+            (*code, profile)
+        })
+        .collect()
+});
+
 // Rust tests
 #[cfg(test)]
 mod tests {
@@ -39,6 +177,80 @@ mod tests {
         assert!(DICTIONARIES.get("words.txt").unwrap().contains("hello"))
     }
 
+    #[test]
+    fn test_quadgrams_load_and_common_beats_rare() {
+        let tion = ENGLISH_QUADGRAMS.log_probabilities.get("TION").unwrap();
+        assert!(*tion > ENGLISH_QUADGRAMS.floor);
+        assert!(!ENGLISH_QUADGRAMS.log_probabilities.contains_key("QXZJ"));
+    }
+
+    #[test]
+    fn test_load_wordlist_file_normalises_words() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/test_fixtures/wordlists/project_names.txt"
+        );
+        let wordlists = load_wordlists(&[path.to_string()]).unwrap();
+        let (name, words) = &wordlists[0];
+        assert_eq!(name, path);
+        assert!(words.contains("zorblax"));
+        assert!(words.contains("quuxcorp"));
+        assert!(words.contains("fluxcapacitor"));
+    }
+
+    #[test]
+    fn test_load_wordlist_directory_loads_every_file() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/test_fixtures/wordlists");
+        let wordlists = load_wordlists(&[path.to_string()]).unwrap();
+        assert_eq!(wordlists.len(), 2);
+        assert!(wordlists
+            .iter()
+            .any(|(name, words)| name.ends_with("products.txt") && words.contains("widgetron")));
+    }
+
+    #[test]
+    fn test_load_wordlists_keeps_the_order_given() {
+        let directory = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/test_fixtures/wordlists");
+        let paths = [
+            format!("{directory}/project_names.txt"),
+            format!("{directory}/products.txt"),
+        ];
+        let wordlists = load_wordlists(&paths).unwrap();
+        let names: Vec<_> = wordlists.iter().map(|(name, _)| name.clone()).collect();
+        assert_eq!(names, paths);
+    }
+
+    #[test]
+    fn test_check_normalise_string_works_with_lowercasing() {
+        let x = normalise_string("Hello Dear");
+        assert_eq!(x, "hello dear")
+    }
+    #[test]
+    fn test_check_normalise_string_works_with_puncuation() {
+        let x = normalise_string("Hello, Dear");
+        assert_eq!(x, "hello dear")
+    }
+    #[test]
+    fn test_check_normalise_string_works_with_messy_puncuation() {
+        let x = normalise_string(".He/ll?O, Dea!r");
+        assert_eq!(x, "hello dear")
+    }
+
+    #[test]
+    fn test_load_wordlist_missing_file_errors() {
+        assert!(load_wordlists(&["this/file/does/not/exist.txt".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_every_supported_language_loads() {
+        for (code, _) in SUPPORTED_LANGUAGES {
+            let profile = LANGUAGES.get(code).unwrap();
+            assert!(!profile.words.is_empty());
+            assert!(!profile.letter_frequencies.is_empty());
+        }
+        assert!(LANGUAGES.get("de").unwrap().words.contains("und"));
+    }
+
     #[test]
     fn test_dictionary_does_not_contain_single_letter_words() {
         assert!(!DICTIONARIES.get("words.txt").unwrap().contains("a"))
@@ -1,5 +1,6 @@
 use crossbeam::channel::{bounded, Receiver};
 use std::sync::atomic::Ordering::Relaxed;
+use std::sync::Arc;
 use std::{
     sync::atomic::AtomicBool,
     thread::{self, sleep},
@@ -8,35 +9,53 @@ use std::{
 
 use crate::cli_pretty_printing::countdown_until_program_ends;
 
-/// Indicate whether timer is paused
-static PAUSED: AtomicBool = AtomicBool::new(false);
-
-/// Start the timer with duration in seconds
-pub fn start(duration: u32) -> Receiver<()> {
+/// Start the timer with duration in seconds.
+/// It doesn't count while `paused` is set, and stops without firing once `stop` is set,
+/// so each search has its own timer which ends with it.
+pub fn start(duration: u32, paused: Arc<AtomicBool>, stop: Arc<AtomicBool>) -> Receiver<()> {
     let (sender, recv) = bounded(1);
     thread::spawn(move || {
         let mut time_spent = 0;
 
         while time_spent < duration {
-            if !PAUSED.load(Relaxed) {
+            if stop.load(Relaxed) {
+                return;
+            }
+            if !paused.load(Relaxed) {
                 sleep(Duration::from_secs(1));
                 time_spent += 1;
                 // Some pretty printing support
                 countdown_until_program_ends(time_spent, duration);
             }
         }
-        sender.send(()).expect("Timer should send succesfully");
+        // The search may have finished as we ran out of time, so nobody is listening
+        if !stop.load(Relaxed) {
+            sender.send(()).ok();
+        }
     });
 
     recv
 }
 
-/// Pause timer
-pub fn pause() {
-    PAUSED.store(true, Relaxed);
-}
+#[cfg(test)]
+mod tests {
+    use super::start;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::{thread::sleep, time::Duration};
+
+    #[test]
+    fn test_timer_fires() {
+        let timer = start(1, Arc::default(), Arc::default());
+        assert!(timer.recv_timeout(Duration::from_secs(3)).is_ok());
+    }
 
-/// Resume timer
-pub fn resume() {
-    PAUSED.store(false, Relaxed);
+    #[test]
+    fn test_stopped_timer_never_fires() {
+        let stop = Arc::new(AtomicBool::new(false));
+        let timer = start(1, Arc::default(), stop.clone());
+        stop.store(true, Ordering::Relaxed);
+        sleep(Duration::from_millis(1500));
+        assert!(timer.try_recv().is_err());
+    }
 }
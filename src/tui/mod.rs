@@ -0,0 +1,548 @@
+//! A full-screen explorer for the search tree.
+//! Instead of waiting for the searcher, you watch the frontier grow, expand or prune
+//! nodes by hand, look at every intermediate text and pin the one you think is the answer.
+//! Nodes are expanded with the same `Decoders::run` and Athena checker the searcher uses.
+
+use std::collections::HashSet;
+use std::io::{self, stdout, Stdout, Write};
+use std::time::Duration;
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Print, Stylize},
+    terminal::{self, ClearType},
+};
+
+use crate::checkers::{
+    athena::Athena,
+    checker_type::{Check, Checker},
+    CheckerTypes,
+};
+use crate::config::{set_global_config, Config};
+use crate::decoders::crack_results::CrackResult;
+use crate::filtration_system::{filter_and_get_decoders, MyResults};
+use crate::DecoderResult;
+
+/// How many lines at the bottom show the selected node
+const DETAILS_HEIGHT: u16 = 9;
+
+/// Where a node is in the search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeState {
+    /// Not decoded yet
+    Frontier,
+    /// Decoded, its children are the results
+    Expanded,
+    /// Pruned by hand, neither it nor its children are searched
+    Pruned,
+    /// The checkers think this is the plaintext
+    Plaintext,
+}
+
+/// A text in the search tree, and how we got it
+struct Node {
+    /// The text at this point of the search
+    text: String,
+    /// The decoder which made this text from its parent, None for the input
+    crack_result: Option<CrackResult>,
+    /// The node this one was decoded from
+    parent: Option<usize>,
+    /// The nodes decoded from this one
+    children: Vec<usize>,
+    /// How many decoders deep this is
+    depth: usize,
+    /// Where the node is in the search
+    state: NodeState,
+    /// Hides the children in the explorer, this doesn't affect the search
+    collapsed: bool,
+}
+
+/// The search tree, kept as a list of nodes which point at each other by index
+struct SearchTree {
+    /// Every node, the input is node 0
+    nodes: Vec<Node>,
+    /// Texts we have already got, so we don't decode them twice
+    seen: HashSet<String>,
+}
+
+impl SearchTree {
+    /// Starts a tree from the input
+    fn new(input: String) -> SearchTree {
+        SearchTree {
+            seen: HashSet::from([input.clone()]),
+            nodes: vec![Node {
+                text: input,
+                crack_result: None,
+                parent: None,
+                children: vec![],
+                depth: 0,
+                state: NodeState::Frontier,
+                collapsed: false,
+            }],
+        }
+    }
+
+    /// Adds a node below the parent
+    fn add_child(
+        &mut self,
+        parent: usize,
+        text: String,
+        crack_result: CrackResult,
+        state: NodeState,
+    ) {
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            text,
+            crack_result: Some(crack_result),
+            parent: Some(parent),
+            children: vec![],
+            depth: self.nodes[parent].depth + 1,
+            state,
+            collapsed: false,
+        });
+        self.nodes[parent].children.push(id);
+    }
+
+    /// The decoders used to get from the input to this node
+    fn path_to(&self, id: usize) -> Vec<CrackResult> {
+        let mut path = Vec::new();
+        let mut current = Some(id);
+        while let Some(node) = current.map(|id| &self.nodes[id]) {
+            path.extend(node.crack_result.iter().cloned());
+            current = node.parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// The node as the searcher would have returned it
+    fn decoder_result(&self, id: usize) -> DecoderResult {
+        DecoderResult {
+            text: vec![self.nodes[id].text.clone()],
+            path: self.path_to(id),
+        }
+    }
+
+    /// Runs every decoder on a frontier node, just like one step of the searcher.
+    /// Returns the plaintext node if the checkers found one.
+    fn expand(&mut self, id: usize) -> Option<usize> {
+        if self.nodes[id].state != NodeState::Frontier {
+            return None;
+        }
+        self.nodes[id].state = NodeState::Expanded;
+        let current = self.decoder_result(id);
+        let checker = CheckerTypes::CheckAthena(Checker::<Athena>::new());
+        match filter_and_get_decoders(&current).run(&current.text[0], checker) {
+            MyResults::Break(result) => {
+                let text = result
+                    .unencrypted_text
+                    .as_ref()
+                    .and_then(|texts| texts.first().cloned())
+                    .unwrap_or_default();
+                self.add_child(id, text, *result, NodeState::Plaintext);
+                Some(self.nodes.len() - 1)
+            }
+            MyResults::Continue(results) => {
+                for (text, crack_result) in
+                    results.into_iter().flat_map(CrackResult::into_candidates)
+                {
+                    // The searcher skips tiny texts too, they can't be decoded further
+                    if text.len() <= 2 || !self.seen.insert(text.clone()) {
+                        continue;
+                    }
+                    self.add_child(id, text, crack_result, NodeState::Frontier);
+                }
+                None
+            }
+        }
+    }
+
+    /// The frontier node the searcher would decode next, the shallowest one outside pruned branches
+    fn next_frontier(&self) -> Option<usize> {
+        let mut level = vec![0];
+        while !level.is_empty() {
+            if let Some(id) = level
+                .iter()
+                .find(|id| self.nodes[**id].state == NodeState::Frontier)
+            {
+                return Some(*id);
+            }
+            level = level
+                .iter()
+                .filter(|id| self.nodes[**id].state != NodeState::Pruned)
+                .flat_map(|id| self.nodes[*id].children.iter().copied())
+                .collect();
+        }
+        None
+    }
+
+    /// How many nodes are waiting to be decoded
+    fn frontier_size(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| node.state == NodeState::Frontier)
+            .count()
+    }
+
+    /// Prunes a node, or puts it back if it's already pruned
+    fn toggle_prune(&mut self, id: usize) {
+        let node = &mut self.nodes[id];
+        node.state = match node.state {
+            NodeState::Pruned if node.children.is_empty() => NodeState::Frontier,
+            NodeState::Pruned => NodeState::Expanded,
+            _ => NodeState::Pruned,
+        };
+    }
+
+    /// The nodes shown in the explorer, in tree order
+    fn visible(&self) -> Vec<usize> {
+        let mut visible = Vec::new();
+        let mut stack = vec![0];
+        while let Some(id) = stack.pop() {
+            visible.push(id);
+            let node = &self.nodes[id];
+            if !node.collapsed && node.state != NodeState::Pruned {
+                stack.extend(node.children.iter().rev());
+            }
+        }
+        visible
+    }
+}
+
+/// Puts the terminal back how we found it, even if we error
+struct TerminalGuard;
+
+impl TerminalGuard {
+    /// Switches to a full-screen raw terminal
+    fn enter() -> io::Result<TerminalGuard> {
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen).ok();
+        terminal::disable_raw_mode().ok();
+    }
+}
+
+/// The explorer's state
+struct Explorer {
+    /// The search tree
+    tree: SearchTree,
+    /// The selected node
+    selected: usize,
+    /// Whether we're expanding the frontier by ourselves
+    auto: bool,
+    /// A message for the status line
+    message: String,
+}
+
+impl Explorer {
+    /// Expands a node, stopping auto mode and selecting the plaintext if we found it
+    fn expand(&mut self, id: usize) {
+        if let Some(plaintext) = self.tree.expand(id) {
+            self.auto = false;
+            self.selected = plaintext;
+            self.message = "The checkers found a plaintext, press y to accept it".to_string();
+        }
+    }
+
+    /// Moves the selection up or down the visible nodes
+    fn move_selection(&mut self, down: bool) {
+        let visible = self.tree.visible();
+        let position = visible
+            .iter()
+            .position(|id| *id == self.selected)
+            .unwrap_or(0);
+        let position = if down {
+            (position + 1).min(visible.len() - 1)
+        } else {
+            position.saturating_sub(1)
+        };
+        self.selected = visible[position];
+    }
+
+    /// Draws the whole screen
+    fn draw(&self, out: &mut Stdout) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let width = usize::from(width);
+        queue!(out, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+
+        let title = format!(
+            " Ares search tree: {} nodes, {} in the frontier{}",
+            self.tree.nodes.len(),
+            self.tree.frontier_size(),
+            if self.auto { " [auto]" } else { "" }
+        );
+        queue!(out, Print(fit(&title, width).reverse()))?;
+
+        // The tree scrolls to keep the selected node on screen
+        let tree_height = usize::from(height.saturating_sub(DETAILS_HEIGHT + 3)).max(1);
+        let visible = self.tree.visible();
+        let selected = visible
+            .iter()
+            .position(|id| *id == self.selected)
+            .unwrap_or(0);
+        let first = selected.saturating_sub(tree_height - 1);
+        for (row, id) in visible.iter().skip(first).take(tree_height).enumerate() {
+            let node = &self.tree.nodes[*id];
+            let marker = match node.state {
+                NodeState::Frontier => "+",
+                NodeState::Expanded if node.collapsed => ">",
+                NodeState::Expanded => "-",
+                NodeState::Pruned => "x",
+                NodeState::Plaintext => "*",
+            };
+            let decoder = node
+                .crack_result
+                .as_ref()
+                .map_or("Input", |result| result.decoder);
+            let line = fit(
+                &format!(
+                    "{}{} {}: {}",
+                    "  ".repeat(node.depth),
+                    marker,
+                    decoder,
+                    one_line(&node.text)
+                ),
+                width,
+            );
+            queue!(out, cursor::MoveTo(0, row as u16 + 1))?;
+            match (*id == self.selected, node.state) {
+                (true, _) => queue!(out, Print(line.reverse()))?,
+                (false, NodeState::Plaintext) => queue!(out, Print(line.green().bold()))?,
+                (false, NodeState::Pruned) => queue!(out, Print(line.dark_grey()))?,
+                (false, _) => queue!(out, Print(line))?,
+            }
+        }
+
+        let details_top = height.saturating_sub(DETAILS_HEIGHT + 1);
+        queue!(
+            out,
+            cursor::MoveTo(0, details_top),
+            Print("─".repeat(width))
+        )?;
+        for (row, line) in self.details(width).iter().enumerate() {
+            queue!(
+                out,
+                cursor::MoveTo(0, details_top + 1 + row as u16),
+                Print(line)
+            )?;
+        }
+
+        let help = if self.message.is_empty() {
+            "↑↓ move  enter expand  n next  space auto  p prune  ←→ fold  y accept  q quit"
+        } else {
+            &self.message
+        };
+        queue!(
+            out,
+            cursor::MoveTo(0, height.saturating_sub(1)),
+            Print(fit(help, width).reverse())
+        )?;
+        out.flush()
+    }
+
+    /// The lines describing the selected node
+    fn details(&self, width: usize) -> Vec<String> {
+        let node = &self.tree.nodes[self.selected];
+        let mut lines = match &node.crack_result {
+            None => vec!["Decoder: none, this is the input".to_string()],
+            Some(result) => {
+                let mut lines = vec![
+                    format!(
+                        "Decoder: {}{}",
+                        result.decoder,
+                        result
+                            .key
+                            .as_ref()
+                            .map_or(String::new(), |key| format!(" (key {key})"))
+                    ),
+                    format!("About: {}", one_line(result.description)),
+                    format!("Link: {}", result.link),
+                ];
+                if result.success {
+                    lines.push(format!(
+                        "Checker: {} with confidence {:.2}",
+                        result.checker_name, result.confidence
+                    ));
+                }
+                lines
+            }
+        };
+        lines.push(format!(
+            "Path: {}",
+            self.tree
+                .path_to(self.selected)
+                .iter()
+                .map(|result| result.decoder)
+                .collect::<Vec<_>>()
+                .join(" → ")
+        ));
+        lines = lines.iter().map(|line| fit(line, width)).collect();
+
+        // Whatever room is left shows as much of the text as fits
+        let text: Vec<char> = one_line(&node.text).chars().collect();
+        let room = usize::from(DETAILS_HEIGHT).saturating_sub(lines.len());
+        lines.extend(
+            text.chunks(width.max(1))
+                .take(room)
+                .map(|chunk| chunk.iter().collect()),
+        );
+        lines
+    }
+
+    /// Reacts to a key, returning Some when the explorer should close
+    fn handle_key(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Option<Option<DecoderResult>> {
+        self.message.clear();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(None),
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return Some(None),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(false),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(true),
+            KeyCode::Enter | KeyCode::Char('e') => self.expand(self.selected),
+            KeyCode::Char('n') => match self.tree.next_frontier() {
+                Some(id) => self.expand(id),
+                None => self.message = "Nothing left to decode".to_string(),
+            },
+            KeyCode::Char(' ') | KeyCode::Char('a') => self.auto = !self.auto,
+            KeyCode::Char('p') => {
+                if self.selected == 0 {
+                    self.message = "The input can't be pruned".to_string();
+                } else {
+                    self.tree.toggle_prune(self.selected);
+                }
+            }
+            KeyCode::Left => self.tree.nodes[self.selected].collapsed = true,
+            KeyCode::Right => self.tree.nodes[self.selected].collapsed = false,
+            KeyCode::Char('y') => return Some(Some(self.tree.decoder_result(self.selected))),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Texts can have newlines, which would break the layout
+fn one_line(text: &str) -> String {
+    text.replace('\n', "⏎").replace('\r', "").replace('\t', " ")
+}
+
+/// Cuts or pads the text to exactly the width of the screen
+fn fit(text: &str, width: usize) -> String {
+    let cut: String = text
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(width)
+        .collect();
+    let length = cut.chars().count();
+    format!("{cut}{}", " ".repeat(width - length))
+}
+
+/// Opens the explorer on the input. Returns the node the user accepted,
+/// or None if they quit.
+/// The human checker is turned off, as the explorer is how the human checks.
+/// # Errors
+/// This errors if the terminal can't be switched to full screen or drawn on.
+pub fn explore(text: &str, mut config: Config) -> io::Result<Option<DecoderResult>> {
+    config.human_checker_on = false;
+    set_global_config(config);
+
+    let _guard = TerminalGuard::enter()?;
+    let mut out = stdout();
+    let mut explorer = Explorer {
+        tree: SearchTree::new(text.to_string()),
+        selected: 0,
+        auto: false,
+        message: String::new(),
+    };
+
+    loop {
+        explorer.draw(&mut out)?;
+        if explorer.auto {
+            match explorer.tree.next_frontier() {
+                Some(id) => explorer.expand(id),
+                None => {
+                    explorer.auto = false;
+                    explorer.message = "Nothing left to decode".to_string();
+                }
+            }
+        }
+        // In auto mode we only peek at the keyboard so we keep searching
+        let wait = if explorer.auto {
+            Duration::ZERO
+        } else {
+            Duration::from_millis(250)
+        };
+        if !event::poll(wait)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            if let Some(result) = explorer.handle_key(key.code, key.modifiers) {
+                return Ok(result);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NodeState, SearchTree};
+
+    #[test]
+    fn test_expand_finds_plaintext() {
+        let mut tree = SearchTree::new("aHR0cHM6Ly93d3cuZ29vZ2xlLmNvbQ==".to_string());
+        let plaintext = tree.expand(0).expect("Base64 should decode to a URL");
+        assert_eq!(tree.nodes[plaintext].text, "https://www.google.com");
+        assert_eq!(tree.nodes[plaintext].state, NodeState::Plaintext);
+        let result = tree.decoder_result(plaintext);
+        assert_eq!(result.path.len(), 1);
+        assert_eq!(result.path[0].decoder, "Base64");
+    }
+
+    #[test]
+    fn test_expand_adds_frontier_children() {
+        let mut tree = SearchTree::new("vjkrerkdnxhrfjekfdjexk".to_string());
+        assert_eq!(tree.next_frontier(), Some(0));
+        assert!(tree.expand(0).is_none());
+        assert_eq!(tree.nodes[0].state, NodeState::Expanded);
+        assert!(!tree.nodes[0].children.is_empty());
+        assert_eq!(tree.next_frontier(), Some(tree.nodes[0].children[0]));
+        // Expanding twice does nothing
+        let children = tree.nodes[0].children.len();
+        tree.expand(0);
+        assert_eq!(tree.nodes[0].children.len(), children);
+    }
+
+    #[test]
+    fn test_pruned_branches_are_skipped() {
+        let mut tree = SearchTree::new("vjkrerkdnxhrfjekfdjexk".to_string());
+        tree.expand(0);
+        let first = tree.nodes[0].children[0];
+        tree.toggle_prune(first);
+        assert_eq!(tree.nodes[first].state, NodeState::Pruned);
+        assert_ne!(tree.next_frontier(), Some(first));
+        tree.toggle_prune(first);
+        assert_eq!(tree.next_frontier(), Some(first));
+    }
+
+    #[test]
+    fn test_visible_hides_collapsed_children() {
+        let mut tree = SearchTree::new("vjkrerkdnxhrfjekfdjexk".to_string());
+        tree.expand(0);
+        assert_eq!(tree.visible().len(), tree.nodes.len());
+        tree.nodes[0].collapsed = true;
+        assert_eq!(tree.visible(), vec![0]);
+    }
+}